@@ -81,7 +81,7 @@ export default function CommunityScreen() {
         rank: entry.rank,
         username: entry.user.substring(0, 12) + "...", // Truncate address for display
         platform: entry.primaryPlatform,
-        score: entry.score,
+        score: Number(entry.score),
         address: entry.user
       }));
       
@@ -1,30 +1,196 @@
+use std::collections::BTreeMap;
+
 use cosmwasm_std::{
-    entry_point, to_json_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+    entry_point, from_json, to_json_binary, Addr, Binary, Decimal, Deps, DepsMut, Empty, Env,
+    Event, MessageInfo, Order, Response, SignedDecimal, StdError, StdResult, Storage,
+};
+use cw2::{get_contract_version, set_contract_version};
+use cw721::{
+    AllNftInfoResponse, ContractInfoResponse, NftInfoResponse, NumTokensResponse, OwnerOfResponse,
+    TokensResponse,
 };
-use cw2::set_contract_version;
+use cw_storage_plus::{Bound, Map, PrimaryKey};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
 
 use crate::error::ContractError;
 use crate::msg::{
-    ConfigResponse, EndorsementResponse, ExecuteMsg, InstantiateMsg, LeaderboardEntry, NftResponse,
-    ProofResponse, QueryMsg, ReputationResponse,
+    ConfigResponse, ConfigUpdate, EndorsementBudgetResponse, EndorsementRequestRole,
+    EndorsementResponse, ExecuteMsg, InstantiateMsg, LeaderboardCursor, LeaderboardEntry,
+    LevelRulesResponse, MerkleProofResponse, MigrateMsg, NftResponse, PenaltyResponse,
+    ProofResponse, QueryMsg, RankResponse, ReputationResponse, ReputationSnapshotResponse,
+    SkillNftExtension, SkillPlatformsResponse, Trait, UserRankResponse,
 };
 use crate::state::{
-    Config, Endorsement, PlatformStats, SkillNft, SkillProof, UserReputation, CONFIG,
-    ENDORSEMENTS, GLOBAL_LEADERBOARD, PLATFORM_LEADERBOARDS, PLATFORM_STATS, PROOFS,
-    REPUTATIONS, SKILL_NFTS, USER_ENDORSEMENTS, USER_NFTS, USER_PROOFS,
+    default_auto_mint, default_cycle_discount, default_endorsement_budget_base,
+    default_endorsement_budget_per_score, default_endorsement_budget_period,
+    default_flag_collusion, default_qualified_endorsement_bonus, default_reciprocal_discount,
+    score_key, score_prefix, Achievement, AchievementAward, AchievementMetric, BadgeCondition,
+    BadgeRule, CollectionInfo, CollusionFlag, CollusionKind, Config, Endorsement,
+    EndorsementBudget, EndorsementRequest, EndorsementRequestStatus, EndorsementStatus,
+    IdentityMigration, LevelRule, MerkleJob, MerkleJobPhase, MerkleLeaf, MerkleRoot,
+    NftLevelChange, Penalty, PlatformStats, ReputationTier, RestrictedAction, SkillData,
+    SkillMetric, SkillNft, SkillProof, StoredScore, TrustJob, TrustJobPhase, UserReputation,
+    ACHIEVEMENTS, ACTIVE_ENDORSEMENT_EDGES, BADGE_RULES, BURNED_NFTS, COLLECTION_INFO,
+    COLLUSION_FLAGS, CONFIG, ENDORSEMENTS, ENDORSEMENT_BUDGETS, ENDORSEMENT_PAIRS,
+    ENDORSEMENT_REQUESTS, GLOBAL_LEADERBOARD_SIZE, GLOBAL_SCORE_COUNTS, GLOBAL_SCORE_INDEX,
+    IDENTITY_MIGRATIONS, LEGACY_GLOBAL_LEADERBOARD, LEGACY_PLATFORM_LEADERBOARDS,
+    LEGACY_PLATFORM_STATS, LEGACY_REPUTATIONS, LEVEL_RULES, MERKLE_JOB, MERKLE_LEAVES,
+    MERKLE_NODES, MERKLE_ROOTS, NFT_LEVEL_HISTORY, NUM_TOKENS, OPEN_ENDORSEMENT_REQUESTS,
+    OWNER_PLATFORM_NFTS, PENALTIES, PENALTY_COUNT, PENDING_ENDORSEMENTS,
+    PLATFORM_LEADERBOARD_SIZES, PLATFORM_SCORE_COUNTS, PLATFORM_SCORE_INDEX, PLATFORM_STATS,
+    PROOFS, PROOF_EXPIRIES, REPUTATIONS, REPUTATION_ACCOUNTS, REPUTATION_TIERS, REQUESTS_RECEIVED,
+    REQUESTS_SENT, REVOKED_NFTS, SCORE_PREFIX_LEVELS, SKILL_NFTS, SKILL_PLATFORMS, SVG_TEMPLATES,
+    TRUST_CURRENT, TRUST_JOB, TRUST_NEXT, TRUST_OUT_WEIGHTS, TRUST_SCORES, USER_ACHIEVEMENTS,
+    USER_DECLINED_ENDORSEMENTS, USER_ENDORSEMENTS, USER_ENDORSEMENTS_GIVEN, USER_NFTS,
+    USER_PENALTIES, USER_PROOFS, USER_REVOKED_ENDORSEMENTS, USER_WITHDRAWN_ENDORSEMENTS,
 };
 
 // Version info for migration info
 const CONTRACT_NAME: &str = "skillexify-proof";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+// First version with decimal scores, score-indexed leaderboards, one NFT per platform,
+// level rules and achievements
+const SCORE_INDEX_VERSION: &str = "0.2.0";
 
 // Supported platforms
 const SUPPORTED_PLATFORMS: &[&str] = &["LeetCode", "GitHub", "Kaggle", "HackerRank", "Stack Overflow"];
 
+// Reputation points awarded by the contract
+const PROOF_POINTS: i64 = 10; // Base score for each proof
+const NEW_PLATFORM_POINTS: i64 = 25; // Bonus for new platform
+const ENDORSER_POINTS: i64 = 5; // Small bonus for giving endorsements
+const MIN_ENDORSER_POINTS: i64 = 50; // Score needed before endorsing others
+
+// Default NFT level rules: (platform, metric, Bronze/Silver/Gold/Platinum minimums)
+const DEFAULT_LEVEL_RULES: &[(&str, SkillMetric, [u64; 4])] = &[
+    ("LeetCode", SkillMetric::TotalSolutions, [50, 150, 300, 500]),
+    ("GitHub", SkillMetric::Stars, [10, 50, 200, 1000]),
+    ("GitHub", SkillMetric::Repositories, [5, 20, 50, 100]),
+    ("GitHub", SkillMetric::Followers, [10, 50, 200, 1000]),
+    ("Kaggle", SkillMetric::Badges, [1, 5, 10, 20]),
+    ("HackerRank", SkillMetric::Badges, [3, 8, 15, 25]),
+    (
+        "HackerRank",
+        SkillMetric::TotalSolutions,
+        [50, 150, 300, 500],
+    ),
+    (
+        "Stack Overflow",
+        SkillMetric::Score,
+        [500, 2000, 10000, 50000],
+    ),
+];
+
+// Default milestones: (id, name, description, metric, threshold)
+const DEFAULT_ACHIEVEMENTS: &[(&str, &str, &str, AchievementMetric, u32)] = &[
+    (
+        "first-proof",
+        "First Proof",
+        "Stored a first verified skill proof",
+        AchievementMetric::TotalProofs,
+        1,
+    ),
+    (
+        "all-platforms",
+        "Verified Everywhere",
+        "Verified on all 5 supported platforms",
+        AchievementMetric::Platforms,
+        5,
+    ),
+    (
+        "endorsed-10",
+        "Well Endorsed",
+        "Received 10 endorsements",
+        AchievementMetric::EndorsementsReceived,
+        10,
+    ),
+    (
+        "endorser-10",
+        "Generous Endorser",
+        "Endorsed 10 people",
+        AchievementMetric::EndorsementsGiven,
+        10,
+    ),
+];
+const MAX_ACHIEVEMENTS: usize = 32;
+
+// Skill NFT level names and badge colours, indexed by level - 1
+const LEVEL_NAMES: [&str; 4] = ["Bronze", "Silver", "Gold", "Platinum"];
+const LEVEL_COLORS: [&str; 4] = ["#cd7f32", "#c0c0c0", "#ffd700", "#e5e4e2"];
+
+// Badge used when no template was uploaded for a platform level
+const DEFAULT_SVG_TEMPLATE: &str = concat!(
+    r##"<svg xmlns="http://www.w3.org/2000/svg" width="320" height="320" viewBox="0 0 320 320">"##,
+    r##"<rect width="320" height="320" rx="24" fill="#111827"/>"##,
+    r##"<circle cx="160" cy="130" r="80" fill="{{level_color}}"/>"##,
+    r##"<text x="160" y="140" font-family="monospace" font-size="24" text-anchor="middle" fill="#111827">{{level_name}}</text>"##,
+    r##"<text x="160" y="250" font-family="monospace" font-size="22" text-anchor="middle" fill="#f9fafb">{{platform}}</text>"##,
+    r##"<text x="160" y="285" font-family="monospace" font-size="14" text-anchor="middle" fill="#9ca3af">{{proof_count}} proofs</text>"##,
+    "</svg>"
+);
+const MAX_SVG_TEMPLATE_BYTES: usize = 16 * 1024;
+
+// Composite badges are held as skill NFTs on a pseudo-platform named after the rule
+const BADGE_PLATFORM_PREFIX: &str = "badge:";
+const BADGE_LEVEL: u32 = 1;
+const MAX_BADGE_RULES: usize = 32;
+const MAX_BADGE_CONDITIONS: usize = 16;
+const MAX_BADGE_CONDITION_DEPTH: u32 = 4;
+
+// Default cw721 collection info
+const DEFAULT_COLLECTION_NAME: &str = "Skillexify Skill Badges";
+const DEFAULT_COLLECTION_SYMBOL: &str = "SKILL";
+
+// Default reputation tiers
+const DEFAULT_TIERS: &[(&str, i64)] = &[
+    ("Novice", 0),
+    ("Intermediate", 100),
+    ("Expert", 500),
+    ("Master", 1000),
+];
+
+// Re-endorsement cooldown for the same (endorser, endorsee, skill)
+const DEFAULT_ENDORSEMENT_COOLDOWN: u64 = 30 * 24 * 60 * 60;
+
+// Upper bound on the accounts an endorsee actively endorses that are checked when looking
+// for A->B->C->A rings. Endorsements past it are flagged as unchecked.
+const MAX_RING_SCAN: usize = 50;
+
+// Endorsement requests a requester may have open at a time
+const MAX_OPEN_ENDORSEMENT_REQUESTS: u32 = 20;
+// Endorsement requests an endorser may have waiting on them at a time
+const MAX_RECEIVED_ENDORSEMENT_REQUESTS: u32 = 50;
+
+// Pagination defaults
+const DEFAULT_PAGE_LIMIT: u32 = 30;
+const MAX_PAGE_LIMIT: u32 = 100;
+
+// Accounts listed on either side of a user by GetUserRank
+const DEFAULT_RANK_NEIGHBORS: u32 = 2;
+const MAX_RANK_NEIGHBORS: u32 = 10;
+
+// Trust propagation job limits
+const DEFAULT_TRUST_ITERATIONS: u32 = 20;
+const MAX_TRUST_ITERATIONS: u32 = 100;
+const DEFAULT_TRUST_DAMPING_PERCENT: u64 = 85;
+const DEFAULT_TRUST_JOB_LIMIT: u32 = 100;
+const MAX_TRUST_JOB_LIMIT: u32 = 1000;
+
+// Merkle export job defaults
+const DEFAULT_MERKLE_JOB_LIMIT: u32 = 100;
+const MAX_MERKLE_JOB_LIMIT: u32 = 1000;
+
+// Leaf and node hashes are prefixed with different bytes, so an internal node can never
+// be passed off as a leaf
+const MERKLE_LEAF_PREFIX: u8 = 0x00;
+const MERKLE_NODE_PREFIX: u8 = 0x01;
+
 #[entry_point]
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
@@ -39,19 +205,227 @@ pub fn instantiate(
         total_proofs: 0,
         total_users: 0,
         supported_platforms: SUPPORTED_PLATFORMS.iter().map(|&s| s.to_string()).collect(),
+        endorsement_cooldown: DEFAULT_ENDORSEMENT_COOLDOWN,
+        endorsement_budget_base: default_endorsement_budget_base(),
+        endorsement_budget_per_score: default_endorsement_budget_per_score(),
+        endorsement_budget_period: default_endorsement_budget_period(),
+        reciprocal_endorsement_discount: default_reciprocal_discount(),
+        cycle_endorsement_discount: default_cycle_discount(),
+        flag_collusion: default_flag_collusion(),
+        strict_skill_endorsements: false,
+        qualified_endorsement_bonus: default_qualified_endorsement_bonus(),
+        require_endorsement_acceptance: false,
+        score_floor: None,
+        dispute_resolvers: vec![],
+        guardians: vec![],
+        guardian_threshold: 0,
+        auto_mint: default_auto_mint(),
+        onchain_metadata: false,
+        proof_validity: None,
+        minters: vec![],
     };
 
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     CONFIG.save(deps.storage, &config)?;
+    COLLECTION_INFO.save(
+        deps.storage,
+        &CollectionInfo {
+            name: msg
+                .name
+                .unwrap_or_else(|| DEFAULT_COLLECTION_NAME.to_string()),
+            symbol: msg
+                .symbol
+                .unwrap_or_else(|| DEFAULT_COLLECTION_SYMBOL.to_string()),
+        },
+    )?;
+    NUM_TOKENS.save(deps.storage, &0)?;
+    save_default_level_rules(deps.storage)?;
+    save_default_achievements(deps.storage)?;
 
-    // Initialize empty global leaderboard
-    GLOBAL_LEADERBOARD.save(deps.storage, &vec![])?;
+    REPUTATION_TIERS.save(deps.storage, &default_tiers(), env.block.height)?;
+    GLOBAL_LEADERBOARD_SIZE.save(deps.storage, &0)?;
 
     Ok(Response::new()
         .add_attribute("method", "instantiate")
         .add_attribute("admin", config.admin))
 }
 
+#[entry_point]
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let stored = get_contract_version(deps.storage)?;
+    if stored.contract != CONTRACT_NAME {
+        return Err(ContractError::InvalidMigration {
+            reason: format!("cannot migrate from {}", stored.contract),
+        });
+    }
+    let from = parse_version(&stored.version)?;
+    if from > parse_version(CONTRACT_VERSION)? {
+        return Err(ContractError::InvalidMigration {
+            reason: format!("cannot downgrade from {}", stored.version),
+        });
+    }
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    // Each step runs once, when upgrading from a version that predates it
+    if from < parse_version(SCORE_INDEX_VERSION)? {
+        migrate_to_score_indexes(deps.storage, &env)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "migrate")
+        .add_attribute("from_version", stored.version))
+}
+
+// (major, minor, patch) of a cw2 version string
+fn parse_version(version: &str) -> StdResult<(u64, u64, u64)> {
+    let invalid = || StdError::generic_err(format!("Invalid contract version: {}", version));
+    let parts = version
+        .split('.')
+        .map(|part| part.parse::<u64>().map_err(|_| invalid()))
+        .collect::<StdResult<Vec<_>>>()?;
+    match parts[..] {
+        [major, minor, patch] => Ok((major, minor, patch)),
+        _ => Err(invalid()),
+    }
+}
+
+// Upgrade a contract stored before SCORE_INDEX_VERSION. This is not resumable: every
+// reputation, platform stat, NFT and endorsement is rewritten in the migrate transaction,
+// and each reputation is also indexed on the global leaderboard and on every platform it
+// holds. Gas grows linearly with those records, so the whole store has to fit in one
+// transaction under the chain's block gas limit; test the upgrade against a copy of the
+// production state before submitting it.
+fn migrate_to_score_indexes(storage: &mut dyn Storage, env: &Env) -> StdResult<()> {
+    // Rewrite integer scores as fixed-point decimals. Records are rewritten in place,
+    // bypassing the snapshot changelog: the value is unchanged.
+    let legacy_reputations = LEGACY_REPUTATIONS
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (user, mut legacy) in legacy_reputations {
+        if let StoredScore::Integer(_) = legacy.score {
+            legacy.score = StoredScore::Decimal(legacy.score.into());
+            LEGACY_REPUTATIONS.save(storage, &user, &legacy)?;
+        }
+    }
+
+    // Tiers did not exist before this version
+    REPUTATION_TIERS.save(storage, &default_tiers(), env.block.height)?;
+
+    // Rewrite platform stats still holding the float average
+    let legacy_stats = LEGACY_PLATFORM_STATS
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (platform, legacy) in legacy_stats {
+        if PLATFORM_STATS.load(storage, platform.clone()).is_err() {
+            let stats = PlatformStats {
+                platform: legacy.platform,
+                total_users: legacy.total_users,
+                total_proofs: legacy.total_proofs,
+                average_score: Decimal::zero(),
+                top_users: legacy.top_users,
+            };
+            PLATFORM_STATS.save(storage, platform, &stats)?;
+        }
+    }
+
+    // Replace the insertion-ordered leaderboards with the score indexes
+    LEGACY_GLOBAL_LEADERBOARD.remove(storage);
+    let platforms = LEGACY_PLATFORM_LEADERBOARDS
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for platform in platforms {
+        LEGACY_PLATFORM_LEADERBOARDS.remove(storage, platform);
+    }
+    let reputations = REPUTATIONS
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, reputation)| reputation))
+        .collect::<StdResult<Vec<_>>>()?;
+    GLOBAL_LEADERBOARD_SIZE.save(storage, &0)?;
+    for reputation in &reputations {
+        REPUTATION_ACCOUNTS.save(storage, &reputation.user, &Empty {})?;
+        index_reputation(storage, reputation)?;
+        count_reputation(storage, None, Some(reputation))?;
+    }
+
+    // Expose existing tokens through the cw721 interface, one token per platform
+    COLLECTION_INFO.save(
+        storage,
+        &CollectionInfo {
+            name: DEFAULT_COLLECTION_NAME.to_string(),
+            symbol: DEFAULT_COLLECTION_SYMBOL.to_string(),
+        },
+    )?;
+    consolidate_skill_nfts(storage, env.block.time.seconds())?;
+
+    save_default_level_rules(storage)?;
+    save_default_achievements(storage)?;
+
+    // Link NFTs to the proofs backing them; older tokens only carried a count
+    let nfts = SKILL_NFTS
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, nft)| nft))
+        .collect::<StdResult<Vec<_>>>()?;
+    for mut nft in nfts {
+        let evidence = supporting_proof_ids(
+            storage,
+            &nft.owner,
+            &nft.platform,
+            nft.skill_level,
+            env.block.time.seconds(),
+        )?;
+        if nft.proof_ids != evidence {
+            nft.proof_count = evidence.len() as u32;
+            nft.proof_ids = evidence;
+            SKILL_NFTS.save(storage, nft.token_id.clone(), &nft)?;
+        }
+    }
+
+    // Backfill the (endorser, endorsee, skill) index with the latest endorsement of each triple
+    let mut endorsements = ENDORSEMENTS
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, endorsement)| endorsement))
+        .collect::<StdResult<Vec<_>>>()?;
+    endorsements.sort_by_key(|endorsement| endorsement.timestamp);
+    for endorsement in &endorsements {
+        let key = (
+            &endorsement.endorser,
+            &endorsement.endorsee,
+            endorsement.skill.as_str(),
+        );
+        let is_latest = match ENDORSEMENT_PAIRS.may_load(storage, key)? {
+            Some(existing_id) => {
+                ENDORSEMENTS.load(storage, existing_id)?.timestamp <= endorsement.timestamp
+            }
+            None => true,
+        };
+        if is_latest {
+            ENDORSEMENT_PAIRS.save(storage, key, &endorsement.id)?;
+        }
+    }
+
+    // Rebuild the endorser-side index
+    let mut given: BTreeMap<Addr, Vec<String>> = BTreeMap::new();
+    for endorsement in &endorsements {
+        given
+            .entry(endorsement.endorser.clone())
+            .or_default()
+            .push(endorsement.id.clone());
+    }
+    for (endorser, endorsement_ids) in given {
+        USER_ENDORSEMENTS_GIVEN.save(storage, &endorser, &endorsement_ids)?;
+    }
+
+    // Index the active endorsements for ring checks
+    for endorsement in &endorsements {
+        if endorsement.status == EndorsementStatus::Active {
+            let edge = (&endorsement.endorser, &endorsement.endorsee);
+            step_count(storage, &ACTIVE_ENDORSEMENT_EDGES, edge, true)?;
+        }
+    }
+
+    Ok(())
+}
+
 #[entry_point]
 pub fn execute(
     deps: DepsMut,
@@ -59,6 +433,10 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+    if let Some(action) = restricted_action(&msg) {
+        assert_not_restricted(deps.storage, &env, &info.sender, action)?;
+    }
+
     match msg {
         ExecuteMsg::StoreProof {
             platform,
@@ -78,16 +456,183 @@ pub fn execute(
             message,
             weight,
         } => execute_add_endorsement(deps, env, info, endorsee, skill, message, weight),
+        ExecuteMsg::RequestEndorsement {
+            endorser,
+            skill,
+            message,
+        } => execute_request_endorsement(deps, env, info, endorser, skill, message),
+        ExecuteMsg::DismissEndorsementRequest { request_id } => {
+            execute_dismiss_endorsement_request(deps, info, request_id)
+        }
+        ExecuteMsg::AcceptEndorsement { endorsement_id } => {
+            execute_accept_endorsement(deps, env, info, endorsement_id)
+        }
+        ExecuteMsg::DeclineEndorsement { endorsement_id } => {
+            execute_decline_endorsement(deps, env, info, endorsement_id)
+        }
+        ExecuteMsg::RevokeEndorsement { endorsement_id } => {
+            execute_revoke_endorsement(deps, env, info, endorsement_id)
+        }
+        ExecuteMsg::UpdateEndorsement {
+            endorsement_id,
+            message,
+            weight,
+        } => execute_update_endorsement(deps, env, info, endorsement_id, message, weight),
         ExecuteMsg::MintSkillNft {
             recipient,
             platform,
             skill_level,
             token_uri,
         } => execute_mint_skill_nft(deps, env, info, recipient, platform, skill_level, token_uri),
+        ExecuteMsg::ClaimSkillNft { platform } => {
+            execute_claim_skill_nft(deps, env, info, platform)
+        }
+        ExecuteMsg::BurnSkillNft { token_id } => execute_burn_skill_nft(deps, env, info, token_id),
+        ExecuteMsg::RevokeSkillNft { token_id, reason } => {
+            execute_revoke_skill_nft(deps, env, info, token_id, reason)
+        }
+        ExecuteMsg::RevokeProof { proof_id, reason } => {
+            execute_revoke_proof(deps, env, info, proof_id, reason)
+        }
+        ExecuteMsg::ProcessExpiredProofs { limit } => {
+            execute_process_expired_proofs(deps, env, limit)
+        }
         ExecuteMsg::UpdateAdmin { new_admin } => execute_update_admin(deps, info, new_admin),
+        ExecuteMsg::UpdateConfig(update) => execute_update_config(deps, info, update),
+        ExecuteMsg::SetReputationTiers { tiers } => {
+            execute_set_reputation_tiers(deps, env, info, tiers)
+        }
+        ExecuteMsg::SetSkillPlatforms { skill, platforms } => {
+            execute_set_skill_platforms(deps, info, skill, platforms)
+        }
+        ExecuteMsg::SetLevelRules { platform, rules } => {
+            execute_set_level_rules(deps, info, platform, rules)
+        }
+        ExecuteMsg::SetSvgTemplate {
+            platform,
+            level,
+            template,
+        } => execute_set_svg_template(deps, info, platform, level, template),
+        ExecuteMsg::SetBadgeRule { rule } => execute_set_badge_rule(deps, info, rule),
+        ExecuteMsg::RemoveBadgeRule { id } => execute_remove_badge_rule(deps, info, id),
+        ExecuteMsg::RefreshBadges { user } => execute_refresh_badges(deps, env, user),
+        ExecuteMsg::SetAchievement { achievement } => {
+            execute_set_achievement(deps, info, achievement)
+        }
+        ExecuteMsg::RemoveAchievement { id } => execute_remove_achievement(deps, info, id),
+        ExecuteMsg::ClearCollusionFlag { user } => execute_clear_collusion_flag(deps, info, user),
+        ExecuteMsg::RunTrustJob {
+            iterations,
+            damping,
+            limit,
+            restart,
+        } => execute_run_trust_job(deps, env, info, iterations, damping, limit, restart),
+        ExecuteMsg::RunMerkleJob { limit } => execute_run_merkle_job(deps, env, info, limit),
+        ExecuteMsg::IssuePenalty {
+            user,
+            score_penalty,
+            restrictions,
+            duration,
+            reason,
+            dispute_id,
+        } => execute_issue_penalty(
+            deps,
+            env,
+            info,
+            user,
+            score_penalty,
+            restrictions,
+            duration,
+            reason,
+            dispute_id,
+        ),
+        ExecuteMsg::LiftPenalty { penalty_id } => execute_lift_penalty(deps, env, info, penalty_id),
+        ExecuteMsg::MigrateIdentity {
+            old_address,
+            new_address,
+        } => execute_migrate_identity(deps, env, info, old_address, new_address),
+        ExecuteMsg::CancelIdentityMigration { old_address } => {
+            execute_cancel_identity_migration(deps, info, old_address)
+        }
+        ExecuteMsg::TransferNft { .. }
+        | ExecuteMsg::SendNft { .. }
+        | ExecuteMsg::Approve { .. }
+        | ExecuteMsg::Revoke { .. }
+        | ExecuteMsg::ApproveAll { .. }
+        | ExecuteMsg::RevokeAll { .. } => Err(ContractError::NonTransferable {}),
+    }
+}
+
+// Action a message performs on behalf of the sender that penalties can freeze. Every
+// message is listed so a new one has to be classified.
+fn restricted_action(msg: &ExecuteMsg) -> Option<RestrictedAction> {
+    match msg {
+        ExecuteMsg::StoreProof { .. } | ExecuteMsg::ClaimSkillNft { .. } => {
+            Some(RestrictedAction::SubmitProof)
+        }
+        ExecuteMsg::AddEndorsement { .. }
+        | ExecuteMsg::UpdateEndorsement { .. }
+        | ExecuteMsg::RequestEndorsement { .. }
+        | ExecuteMsg::AcceptEndorsement { .. } => Some(RestrictedAction::Endorse),
+        // Withdrawing from the sender's own records; a restricted account can always back out
+        ExecuteMsg::DeclineEndorsement { .. }
+        | ExecuteMsg::DismissEndorsementRequest { .. }
+        | ExecuteMsg::RevokeEndorsement { .. }
+        | ExecuteMsg::RevokeProof { .. }
+        | ExecuteMsg::BurnSkillNft { .. } => None,
+        // Housekeeping anyone may run; it only applies rules already in force
+        ExecuteMsg::ProcessExpiredProofs { .. } | ExecuteMsg::RefreshBadges { .. } => None,
+        // Held to their own roles (admin, NFT authority, dispute resolvers, guardians)
+        ExecuteMsg::UpdateReputation { .. }
+        | ExecuteMsg::MintSkillNft { .. }
+        | ExecuteMsg::RevokeSkillNft { .. }
+        | ExecuteMsg::UpdateAdmin { .. }
+        | ExecuteMsg::UpdateConfig(_)
+        | ExecuteMsg::SetReputationTiers { .. }
+        | ExecuteMsg::SetSkillPlatforms { .. }
+        | ExecuteMsg::SetLevelRules { .. }
+        | ExecuteMsg::SetSvgTemplate { .. }
+        | ExecuteMsg::SetBadgeRule { .. }
+        | ExecuteMsg::RemoveBadgeRule { .. }
+        | ExecuteMsg::SetAchievement { .. }
+        | ExecuteMsg::RemoveAchievement { .. }
+        | ExecuteMsg::ClearCollusionFlag { .. }
+        | ExecuteMsg::RunTrustJob { .. }
+        | ExecuteMsg::RunMerkleJob { .. }
+        | ExecuteMsg::IssuePenalty { .. }
+        | ExecuteMsg::LiftPenalty { .. }
+        | ExecuteMsg::MigrateIdentity { .. }
+        | ExecuteMsg::CancelIdentityMigration { .. } => None,
+        // Rejected for every sender
+        ExecuteMsg::TransferNft { .. }
+        | ExecuteMsg::SendNft { .. }
+        | ExecuteMsg::Approve { .. }
+        | ExecuteMsg::Revoke { .. }
+        | ExecuteMsg::ApproveAll { .. }
+        | ExecuteMsg::RevokeAll { .. } => None,
+    }
+}
+
+fn assert_not_restricted(
+    storage: &dyn Storage,
+    env: &Env,
+    user: &Addr,
+    action: RestrictedAction,
+) -> Result<(), ContractError> {
+    let now = env.block.time.seconds();
+    for penalty_id in USER_PENALTIES.may_load(storage, user)?.unwrap_or_default() {
+        let penalty = PENALTIES.load(storage, penalty_id)?;
+        if penalty.is_active(now) && penalty.restrictions.contains(&action) {
+            return Err(ContractError::AccountRestricted {
+                action: action.to_string(),
+                penalty_id,
+            });
+        }
     }
+    Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn execute_store_proof(
     deps: DepsMut,
     env: Env,
@@ -131,10 +676,18 @@ pub fn execute_store_proof(
         timestamp: env.block.time.seconds(),
         verified: true, // In real implementation, this would be set after zkTLS verification
         metadata,
+        expires_at: config
+            .proof_validity
+            .map(|validity| env.block.time.seconds().saturating_add(validity)),
+        revoked_at: None,
+        revocation_reason: None,
     };
 
     // Store proof
     PROOFS.save(deps.storage, proof_id.clone(), &proof)?;
+    if let Some(expires_at) = proof.expires_at {
+        PROOF_EXPIRIES.save(deps.storage, (expires_at, &proof_id), &Empty {})?;
+    }
 
     // Update user proofs index
     let mut user_proofs = USER_PROOFS
@@ -143,12 +696,48 @@ pub fn execute_store_proof(
     user_proofs.push(proof_id.clone());
     USER_PROOFS.save(deps.storage, &info.sender, &user_proofs)?;
 
+    // Keep the platform's NFT backed by this proof, and raise it to the level the proofs earn
+    // unless the owner burned it or it was revoked
+    refresh_platform_nft(deps.storage, &env, &info.sender, &platform)?;
+    let mut nft_change = None;
+    if config.auto_mint
+        && !BURNED_NFTS.has(deps.storage, (&info.sender, &platform))
+        && !REVOKED_NFTS.has(deps.storage, (&info.sender, &platform))
+    {
+        let (level, proof_ids) = earned_level(
+            deps.storage,
+            &info.sender,
+            &platform,
+            env.block.time.seconds(),
+        )?;
+        if level
+            > held_level(
+                deps.storage,
+                &info.sender,
+                &platform,
+                env.block.time.seconds(),
+            )?
+        {
+            let (token_id, change) = save_skill_nft(
+                deps.storage,
+                &env,
+                &info.sender,
+                &platform,
+                level,
+                None,
+                proof_ids,
+                &env.contract.address,
+            )?;
+            nft_change = change.map(|change| (token_id, change));
+        }
+    }
+
     // Update or create user reputation
     let mut reputation = REPUTATIONS
         .may_load(deps.storage, &info.sender)?
         .unwrap_or_else(|| UserReputation {
             user: info.sender.clone(),
-            score: 0,
+            score: SignedDecimal::zero(),
             total_proofs: 0,
             endorsements_received: 0,
             endorsements_given: 0,
@@ -157,14 +746,19 @@ pub fn execute_store_proof(
         });
 
     reputation.total_proofs += 1;
-    reputation.score += 10; // Base score for each proof
+    reputation.score = add_score(&reputation, score_points(PROOF_POINTS), "store_proof")?;
     reputation.last_updated = env.block.time.seconds();
     if !reputation.platforms.contains(&platform) {
         reputation.platforms.push(platform.clone());
-        reputation.score += 25; // Bonus for new platform
+        reputation.score = add_score(
+            &reputation,
+            score_points(NEW_PLATFORM_POINTS),
+            "store_proof",
+        )?;
     }
 
-    REPUTATIONS.save(deps.storage, &info.sender, &reputation)?;
+    let mut events = save_reputation(deps.storage, &env, &reputation)?;
+    events.extend(evaluate_badges(deps.storage, &env, &info.sender)?);
 
     // Update platform stats
     let platform_clone = platform.clone();
@@ -174,7 +768,7 @@ pub fn execute_store_proof(
             platform: platform_clone.clone(),
             total_users: 0,
             total_proofs: 0,
-            average_score: 0.0,
+            average_score: Decimal::zero(),
             top_users: vec![],
         });
 
@@ -191,15 +785,349 @@ pub fn execute_store_proof(
     config.total_proofs += 1;
     CONFIG.save(deps.storage, &config)?;
 
-    // Update leaderboards (simplified version)
-    update_leaderboards(deps.storage, &platform, &info.sender)?;
-
-    Ok(Response::new()
+    let mut response = Response::new()
+        .add_events(events)
         .add_attribute("method", "store_proof")
         .add_attribute("proof_id", proof_id)
         .add_attribute("user", info.sender)
         .add_attribute("platform", platform)
-        .add_attribute("score_gained", "10"))
+        .add_attribute("score_gained", PROOF_POINTS.to_string());
+    if let Some((token_id, change)) = nft_change {
+        response = response
+            .add_attribute("nft_token_id", token_id)
+            .add_attribute("nft_from_level", change.from_level.to_string())
+            .add_attribute("nft_to_level", change.to_level.to_string());
+    }
+    Ok(response)
+}
+
+// Highest level any of the platform's rules grants for the proof's statistics. Skill data
+// that does not parse as `SkillData` still stores the proof, it just earns no level.
+fn skill_level_for_proof(
+    storage: &dyn Storage,
+    platform: &str,
+    skill_data: &str,
+) -> StdResult<u32> {
+    let Ok(data) = from_json::<SkillData>(skill_data.as_bytes()) else {
+        return Ok(0);
+    };
+    let rules = LEVEL_RULES.may_load(storage, platform)?.unwrap_or_default();
+    Ok(rules
+        .iter()
+        .filter_map(|rule| {
+            let value = data.metric(rule.metric)?;
+            Some(rule.thresholds.iter().filter(|&&min| value >= min).count() as u32)
+        })
+        .max()
+        .unwrap_or(0))
+}
+
+// The level each of the user's active verified proofs on the platform earns, oldest first
+fn proof_levels(
+    storage: &dyn Storage,
+    user: &Addr,
+    platform: &str,
+    now: u64,
+) -> StdResult<Vec<(String, u32)>> {
+    let mut levels = vec![];
+    for proof_id in USER_PROOFS.may_load(storage, user)?.unwrap_or_default() {
+        let proof = PROOFS.load(storage, proof_id)?;
+        if proof.platform == platform && proof.verified && proof.is_active(now) {
+            let level = skill_level_for_proof(storage, platform, &proof.skill_data)?;
+            levels.push((proof.id, level));
+        }
+    }
+    Ok(levels)
+}
+
+// The best level among the user's active verified proofs on the platform, and the proofs
+// that earn it. Claims and auto-mints both follow this rule.
+fn earned_level(
+    storage: &dyn Storage,
+    user: &Addr,
+    platform: &str,
+    now: u64,
+) -> StdResult<(u32, Vec<String>)> {
+    let levels = proof_levels(storage, user, platform, now)?;
+    let level = levels.iter().map(|(_, level)| *level).max().unwrap_or(0);
+    let proof_ids = levels
+        .into_iter()
+        .filter(|(_, proof_level)| level > 0 && *proof_level == level)
+        .map(|(proof_id, _)| proof_id)
+        .collect();
+    Ok((level, proof_ids))
+}
+
+// The user's active verified proofs on the platform that earn at least `skill_level`
+fn supporting_proof_ids(
+    storage: &dyn Storage,
+    user: &Addr,
+    platform: &str,
+    skill_level: u32,
+    now: u64,
+) -> StdResult<Vec<String>> {
+    Ok(proof_levels(storage, user, platform, now)?
+        .into_iter()
+        .filter(|(_, level)| skill_level > 0 && *level >= skill_level)
+        .map(|(proof_id, _)| proof_id)
+        .collect())
+}
+
+// Level of the NFT the owner holds for the platform as of `now`, or 0 without one
+fn held_level(storage: &dyn Storage, owner: &Addr, platform: &str, now: u64) -> StdResult<u32> {
+    match OWNER_PLATFORM_NFTS.may_load(storage, (owner, platform))? {
+        Some(token_id) => {
+            Ok(live_skill_nft(storage, SKILL_NFTS.load(storage, token_id)?, now)?.skill_level)
+        }
+        None => Ok(0),
+    }
+}
+
+// The NFT as its proofs back it at `now`. Proofs that expired since the last save stop
+// counting as evidence right away, and the level drops to what the remaining proofs earn,
+// without waiting for ProcessExpiredProofs to persist the change.
+fn live_skill_nft(storage: &dyn Storage, mut nft: SkillNft, now: u64) -> StdResult<SkillNft> {
+    let mut active = vec![];
+    for proof_id in &nft.proof_ids {
+        if PROOFS.load(storage, proof_id.clone())?.is_active(now) {
+            active.push(proof_id.clone());
+        }
+    }
+    if active.len() == nft.proof_ids.len() {
+        return Ok(nft);
+    }
+
+    let (level, _) = earned_level(storage, &nft.owner, &nft.platform, now)?;
+    if level > 0 && level < nft.skill_level {
+        nft.skill_level = level;
+        active = supporting_proof_ids(storage, &nft.owner, &nft.platform, level, now)?;
+    }
+    nft.proof_count = active.len() as u32;
+    nft.proof_ids = active;
+    Ok(nft)
+}
+
+// The user's proofs on the platform that are neither revoked nor expired, oldest first
+fn platform_proof_ids(
+    storage: &dyn Storage,
+    user: &Addr,
+    platform: &str,
+    now: u64,
+) -> StdResult<Vec<String>> {
+    let mut proof_ids = vec![];
+    for proof_id in USER_PROOFS.may_load(storage, user)?.unwrap_or_default() {
+        let proof = PROOFS.load(storage, proof_id)?;
+        if proof.platform == platform && proof.is_active(now) {
+            proof_ids.push(proof.id);
+        }
+    }
+    Ok(proof_ids)
+}
+
+// Sync the proofs linked to the NFT the user holds for the platform with the ones that
+// earn its current level
+fn refresh_platform_nft(
+    storage: &mut dyn Storage,
+    env: &Env,
+    owner: &Addr,
+    platform: &str,
+) -> StdResult<()> {
+    let Some(token_id) = OWNER_PLATFORM_NFTS.may_load(storage, (owner, platform))? else {
+        return Ok(());
+    };
+    let mut nft = SKILL_NFTS.load(storage, token_id.clone())?;
+    let evidence = supporting_proof_ids(
+        storage,
+        owner,
+        platform,
+        nft.skill_level,
+        env.block.time.seconds(),
+    )?;
+    if nft.proof_ids != evidence {
+        nft.proof_count = evidence.len() as u32;
+        nft.proof_ids = evidence;
+        nft.last_updated = env.block.time.seconds();
+        SKILL_NFTS.save(storage, token_id, &nft)?;
+    }
+    Ok(())
+}
+
+// Mint the owner's NFT for a platform, or move the existing one to `skill_level` in place,
+// linking it to `proof_ids` as the proofs that earn the level. Returns the token id and
+// the recorded level change, if any.
+#[allow(clippy::too_many_arguments)]
+fn save_skill_nft(
+    storage: &mut dyn Storage,
+    env: &Env,
+    owner: &Addr,
+    platform: &str,
+    skill_level: u32,
+    token_uri: Option<String>,
+    proof_ids: Vec<String>,
+    attestor: &Addr,
+) -> StdResult<(String, Option<NftLevelChange>)> {
+    let existing = match OWNER_PLATFORM_NFTS.may_load(storage, (owner, platform))? {
+        Some(token_id) => Some(SKILL_NFTS.load(storage, token_id)?),
+        None => None,
+    };
+
+    let (nft, from_level) = match existing {
+        Some(mut nft) => {
+            let from_level = nft.skill_level;
+            if from_level != skill_level {
+                nft.attestor = Some(attestor.clone());
+            }
+            nft.skill_level = skill_level;
+            nft.proof_count = proof_ids.len() as u32;
+            nft.proof_ids = proof_ids.clone();
+            nft.last_updated = env.block.time.seconds();
+            if let Some(token_uri) = token_uri {
+                nft.token_uri = token_uri;
+            }
+            (nft, from_level)
+        }
+        None => {
+            let token_id = format!("{}:{}", owner, platform);
+            let nft = SkillNft {
+                token_id: token_id.clone(),
+                owner: owner.clone(),
+                platform: platform.to_string(),
+                skill_level,
+                token_uri: token_uri.unwrap_or_default(),
+                created_at: env.block.time.seconds(),
+                last_updated: env.block.time.seconds(),
+                proof_count: proof_ids.len() as u32,
+                proof_ids: proof_ids.clone(),
+                attestor: Some(attestor.clone()),
+            };
+
+            OWNER_PLATFORM_NFTS.save(storage, (owner, platform), &token_id)?;
+            let mut user_nfts = USER_NFTS.may_load(storage, owner)?.unwrap_or_default();
+            user_nfts.push(token_id);
+            USER_NFTS.save(storage, owner, &user_nfts)?;
+            NUM_TOKENS.update(storage, |count| -> StdResult<_> { Ok(count + 1) })?;
+            (nft, 0)
+        }
+    };
+    SKILL_NFTS.save(storage, nft.token_id.clone(), &nft)?;
+
+    if from_level == skill_level {
+        return Ok((nft.token_id, None));
+    }
+    let change = NftLevelChange {
+        from_level,
+        to_level: skill_level,
+        changed_at: env.block.time.seconds(),
+        proof_ids,
+    };
+    let mut history = NFT_LEVEL_HISTORY
+        .may_load(storage, &nft.token_id)?
+        .unwrap_or_default();
+    history.push(change.clone());
+    NFT_LEVEL_HISTORY.save(storage, &nft.token_id, &history)?;
+
+    Ok((nft.token_id, Some(change)))
+}
+
+// Fold the per-level tokens of each (owner, platform) into a single token. The highest
+// level wins and the older tokens are recorded as level history.
+fn consolidate_skill_nfts(storage: &mut dyn Storage, now: u64) -> StdResult<()> {
+    let mut groups: BTreeMap<(Addr, String), Vec<SkillNft>> = BTreeMap::new();
+    for item in SKILL_NFTS.range(storage, None, None, Order::Ascending) {
+        let (_, nft) = item?;
+        groups
+            .entry((nft.owner.clone(), nft.platform.clone()))
+            .or_default()
+            .push(nft);
+    }
+
+    let mut user_nfts: BTreeMap<Addr, Vec<String>> = BTreeMap::new();
+    for ((owner, platform), mut nfts) in groups {
+        nfts.sort_by_key(|nft| (nft.created_at, nft.skill_level));
+        for nft in &nfts {
+            SKILL_NFTS.remove(storage, nft.token_id.clone());
+        }
+
+        let mut history = vec![];
+        let mut level = 0;
+        for nft in &nfts {
+            if nft.skill_level > level {
+                history.push(NftLevelChange {
+                    from_level: level,
+                    to_level: nft.skill_level,
+                    changed_at: nft.created_at,
+                    proof_ids: vec![],
+                });
+                level = nft.skill_level;
+            }
+        }
+
+        let token_id = format!("{}:{}", owner, platform);
+        let top = nfts.iter().max_by_key(|nft| nft.skill_level).cloned();
+        if let Some(top) = top {
+            let evidence = supporting_proof_ids(storage, &owner, &platform, top.skill_level, now)?;
+            let nft = SkillNft {
+                token_id: token_id.clone(),
+                created_at: nfts[0].created_at,
+                last_updated: nfts
+                    .iter()
+                    .map(|nft| nft.last_updated)
+                    .max()
+                    .unwrap_or(top.last_updated),
+                proof_count: evidence.len() as u32,
+                proof_ids: evidence,
+                ..top
+            };
+            SKILL_NFTS.save(storage, token_id.clone(), &nft)?;
+            OWNER_PLATFORM_NFTS.save(storage, (&owner, &platform), &token_id)?;
+            NFT_LEVEL_HISTORY.save(storage, &token_id, &history)?;
+            user_nfts.entry(owner).or_default().push(token_id);
+        }
+    }
+
+    let owners = USER_NFTS
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for owner in owners {
+        USER_NFTS.remove(storage, &owner);
+    }
+    let mut num_tokens = 0;
+    for (owner, token_ids) in user_nfts {
+        num_tokens += token_ids.len() as u64;
+        USER_NFTS.save(storage, &owner, &token_ids)?;
+    }
+    NUM_TOKENS.save(storage, &num_tokens)
+}
+
+fn save_default_level_rules(storage: &mut dyn Storage) -> StdResult<()> {
+    let mut rules: BTreeMap<&str, Vec<LevelRule>> = BTreeMap::new();
+    for &(platform, metric, thresholds) in DEFAULT_LEVEL_RULES {
+        rules.entry(platform).or_default().push(LevelRule {
+            metric,
+            thresholds: thresholds.to_vec(),
+        });
+    }
+    for (platform, rules) in rules {
+        LEVEL_RULES.save(storage, platform, &rules)?;
+    }
+    Ok(())
+}
+
+fn save_default_achievements(storage: &mut dyn Storage) -> StdResult<()> {
+    for &(id, name, description, metric, threshold) in DEFAULT_ACHIEVEMENTS {
+        ACHIEVEMENTS.save(
+            storage,
+            id,
+            &Achievement {
+                id: id.to_string(),
+                name: name.to_string(),
+                description: description.to_string(),
+                metric,
+                threshold,
+            },
+        )?;
+    }
+    Ok(())
 }
 
 pub fn execute_add_endorsement(
@@ -223,18 +1151,80 @@ pub fn execute_add_endorsement(
         return Err(ContractError::InvalidEndorsementWeight { weight });
     }
 
+    // One endorsement per (endorser, endorsee, skill), re-endorsable after the cooldown
+    let config = CONFIG.load(deps.storage)?;
+    let pair_key = (&info.sender, &endorsee_addr, skill.as_str());
+    if let Some(previous_id) = ENDORSEMENT_PAIRS.may_load(deps.storage, pair_key)? {
+        let previous = ENDORSEMENTS.load(deps.storage, previous_id)?;
+        if matches!(
+            previous.status,
+            EndorsementStatus::Active | EndorsementStatus::Pending
+        ) {
+            return Err(ContractError::DuplicateEndorsement {});
+        }
+
+        // Counted from when it was revoked, declined or withdrawn, not from when it was given
+        let available_at = previous
+            .updated_at
+            .unwrap_or(previous.timestamp)
+            .saturating_add(config.endorsement_cooldown);
+        if env.block.time.seconds() < available_at {
+            return Err(ContractError::EndorsementCooldown { available_at });
+        }
+    }
+
     // Check if endorser has sufficient reputation (optional)
     let endorser_reputation = REPUTATIONS.may_load(deps.storage, &info.sender)?;
     if let Some(rep) = endorser_reputation {
-        if rep.score < 50 {
+        if rep.score < score_points(MIN_ENDORSER_POINTS) {
             return Err(ContractError::InsufficientReputation {});
         }
     } else {
         return Err(ContractError::InsufficientReputation {});
     }
 
+    // Endorsers proven on platforms related to the skill carry more weight
+    let qualified = is_qualified_endorser(deps.storage, &env, &info.sender, &skill)?;
+    if qualified == Some(false) && config.strict_skill_endorsements {
+        return Err(ContractError::UnqualifiedEndorser { skill });
+    }
+    let bonus = if qualified == Some(true) {
+        config.qualified_endorsement_bonus
+    } else {
+        Decimal::zero()
+    };
+
+    // Spend the endorsement weight from the endorser's budget
+    spend_endorsement_budget(deps.storage, &config, &env, &info.sender, weight)?;
+
     // Generate endorsement ID
-    let endorsement_id = format!("{}:{}:{}", info.sender, endorsee_addr, env.block.time.seconds());
+    let endorsement_id = format!(
+        "{}:{}:{}:{}",
+        info.sender,
+        endorsee_addr,
+        skill,
+        env.block.time.seconds()
+    );
+    if ENDORSEMENTS.has(deps.storage, endorsement_id.clone()) {
+        return Err(ContractError::DuplicateEndorsement {});
+    }
+
+    // Endorsements the endorsee asked for count right away; others may need acceptance
+    let request_key = (&info.sender, &endorsee_addr, skill.as_str());
+    let request_id = OPEN_ENDORSEMENT_REQUESTS.may_load(deps.storage, request_key)?;
+    let status = if request_id.is_none() && config.require_endorsement_acceptance {
+        EndorsementStatus::Pending
+    } else {
+        EndorsementStatus::Active
+    };
+
+    // Pending endorsements are checked for rings once accepted
+    let (collusion, discount) = match status {
+        EndorsementStatus::Active => {
+            check_collusion(deps.storage, &env, &config, &info.sender, &endorsee_addr)?
+        }
+        _ => (None, Decimal::zero()),
+    };
 
     // Create endorsement
     let endorsement = Endorsement {
@@ -245,24 +1235,124 @@ pub fn execute_add_endorsement(
         message,
         weight,
         timestamp: env.block.time.seconds(),
+        status: status.clone(),
+        updated_at: None,
+        discount,
+        bonus,
     };
 
     // Store endorsement
     ENDORSEMENTS.save(deps.storage, endorsement_id.clone(), &endorsement)?;
+    ENDORSEMENT_PAIRS.save(deps.storage, pair_key, &endorsement_id)?;
+
+    // Update endorser's endorsements given index
+    let mut given_endorsements = USER_ENDORSEMENTS_GIVEN
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    given_endorsements.push(endorsement_id.clone());
+    USER_ENDORSEMENTS_GIVEN.save(deps.storage, &info.sender, &given_endorsements)?;
+
+    if let Some(request_id) = request_id {
+        fulfill_endorsement_request(deps.storage, &request_id, &endorsement_id)?;
+    }
+
+    let mut events = vec![];
+    if status == EndorsementStatus::Active {
+        events = apply_endorsement(deps.storage, &env, &endorsement)?;
+    } else {
+        let mut pending = PENDING_ENDORSEMENTS
+            .may_load(deps.storage, &endorsee_addr)?
+            .unwrap_or_default();
+        pending.push(endorsement_id.clone());
+        PENDING_ENDORSEMENTS.save(deps.storage, &endorsee_addr, &pending)?;
+    }
+
+    Ok(Response::new()
+        .add_events(events)
+        .add_attribute("method", "add_endorsement")
+        .add_attribute("endorsement_id", endorsement_id)
+        .add_attribute("endorser", info.sender)
+        .add_attribute("endorsee", endorsee_addr)
+        .add_attribute("skill", skill)
+        .add_attribute("weight", weight.to_string())
+        .add_attribute(
+            "effective_weight",
+            effective_weight(&endorsement, "add_endorsement")?.to_string(),
+        )
+        .add_attribute("qualified", (qualified == Some(true)).to_string())
+        .add_attribute(
+            "status",
+            if status == EndorsementStatus::Active {
+                "active"
+            } else {
+                "pending"
+            },
+        )
+        .add_attribute("collusion", collusion_name(collusion)))
+}
 
+// Discount an endorsement that closes a ring as it becomes active, and flag the accounts
+// involved. Returns the ring found, if any, and the discount to store.
+fn check_collusion(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+    endorser: &Addr,
+    endorsee: &Addr,
+) -> StdResult<(Option<CollusionKind>, Decimal)> {
+    let Some((kind, others)) = detect_collusion(storage, endorser, endorsee)? else {
+        return Ok((None, Decimal::zero()));
+    };
+    let discount = match kind {
+        CollusionKind::Reciprocal => config.reciprocal_endorsement_discount,
+        CollusionKind::Cycle => config.cycle_endorsement_discount,
+        CollusionKind::Unchecked => Decimal::zero(),
+    };
+    if config.flag_collusion {
+        let mut ring = vec![endorser.clone(), endorsee.clone()];
+        ring.extend(others);
+        for member in &ring {
+            let counterparties = ring
+                .iter()
+                .filter(|addr| *addr != member)
+                .cloned()
+                .collect();
+            flag_collusion(storage, env, member, kind.clone(), counterparties)?;
+        }
+    }
+    Ok((Some(kind), discount))
+}
+
+fn collusion_name(collusion: Option<CollusionKind>) -> &'static str {
+    match collusion {
+        Some(CollusionKind::Reciprocal) => "reciprocal",
+        Some(CollusionKind::Cycle) => "cycle",
+        Some(CollusionKind::Unchecked) => "unchecked",
+        None => "none",
+    }
+}
+
+// Count an endorsement toward both parties' reputation
+fn apply_endorsement(
+    storage: &mut dyn Storage,
+    env: &Env,
+    endorsement: &Endorsement,
+) -> Result<Vec<Event>, ContractError> {
     // Update endorsee's endorsements index
     let mut user_endorsements = USER_ENDORSEMENTS
-        .may_load(deps.storage, &endorsee_addr)?
+        .may_load(storage, &endorsement.endorsee)?
         .unwrap_or_default();
-    user_endorsements.push(endorsement_id.clone());
-    USER_ENDORSEMENTS.save(deps.storage, &endorsee_addr, &user_endorsements)?;
+    user_endorsements.push(endorsement.id.clone());
+    USER_ENDORSEMENTS.save(storage, &endorsement.endorsee, &user_endorsements)?;
+    let edge = (&endorsement.endorser, &endorsement.endorsee);
+    step_count(storage, &ACTIVE_ENDORSEMENT_EDGES, edge, true)?;
 
     // Update reputations
     let mut endorsee_rep = REPUTATIONS
-        .may_load(deps.storage, &endorsee_addr)?
+        .may_load(storage, &endorsement.endorsee)?
         .unwrap_or_else(|| UserReputation {
-            user: endorsee_addr.clone(),
-            score: 0,
+            user: endorsement.endorsee.clone(),
+            score: SignedDecimal::zero(),
             total_proofs: 0,
             endorsements_received: 0,
             endorsements_given: 0,
@@ -270,117 +1360,2572 @@ pub fn execute_add_endorsement(
             platforms: vec![],
         });
 
-    let mut endorser_rep = REPUTATIONS.load(deps.storage, &info.sender)?;
+    let mut endorser_rep = REPUTATIONS.load(storage, &endorsement.endorser)?;
 
     endorsee_rep.endorsements_received += 1;
-    endorsee_rep.score += weight as i32;
+    let points = weight_points(endorsement, "endorse")?;
+    endorsee_rep.score = add_score(&endorsee_rep, points, "endorse")?;
     endorsee_rep.last_updated = env.block.time.seconds();
 
     endorser_rep.endorsements_given += 1;
-    endorser_rep.score += 5; // Small bonus for giving endorsements
+    endorser_rep.score = add_score(&endorser_rep, score_points(ENDORSER_POINTS), "endorse")?;
     endorser_rep.last_updated = env.block.time.seconds();
 
-    REPUTATIONS.save(deps.storage, &endorsee_addr, &endorsee_rep)?;
-    REPUTATIONS.save(deps.storage, &info.sender, &endorser_rep)?;
+    let mut events = save_reputation(storage, env, &endorsee_rep)?;
+    events.extend(save_reputation(storage, env, &endorser_rep)?);
+    events.extend(evaluate_badges(storage, env, &endorsement.endorsee)?);
 
-    Ok(Response::new()
-        .add_attribute("method", "add_endorsement")
-        .add_attribute("endorsement_id", endorsement_id)
-        .add_attribute("endorser", info.sender)
-        .add_attribute("endorsee", endorsee_addr)
-        .add_attribute("skill", skill)
-        .add_attribute("weight", weight.to_string()))
+    Ok(events)
 }
 
-pub fn execute_mint_skill_nft(
-    deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    recipient: String,
-    platform: String,
-    skill_level: u32,
-    token_uri: String,
-) -> Result<Response, ContractError> {
-    // Only admin can mint NFTs initially (in production, this would be automated)
-    let config = CONFIG.load(deps.storage)?;
-    if info.sender != config.admin {
-        return Err(ContractError::Unauthorized {});
-    }
+// Undo `apply_endorsement`
+fn reverse_endorsement(
+    storage: &mut dyn Storage,
+    env: &Env,
+    endorsement: &Endorsement,
+) -> Result<Vec<Event>, ContractError> {
+    USER_ENDORSEMENTS.update(storage, &endorsement.endorsee, |ids| -> StdResult<_> {
+        let mut ids = ids.unwrap_or_default();
+        ids.retain(|id| id != &endorsement.id);
+        Ok(ids)
+    })?;
+    let edge = (&endorsement.endorser, &endorsement.endorsee);
+    step_count(storage, &ACTIVE_ENDORSEMENT_EDGES, edge, false)?;
 
-    let recipient_addr = deps.api.addr_validate(&recipient)?;
+    let mut endorsee_rep = REPUTATIONS.load(storage, &endorsement.endorsee)?;
+    endorsee_rep.endorsements_received = endorsee_rep.endorsements_received.saturating_sub(1);
+    let points = weight_points(endorsement, "revoke_endorsement")?;
+    endorsee_rep.score = sub_score(&endorsee_rep, points, "revoke_endorsement")?;
+    endorsee_rep.last_updated = env.block.time.seconds();
+    let mut events = save_reputation(storage, env, &endorsee_rep)?;
 
-    // Validate skill level
-    if skill_level < 1 || skill_level > 4 {
-        return Err(ContractError::InvalidSkillLevel { level: skill_level });
+    let mut endorser_rep = REPUTATIONS.load(storage, &endorsement.endorser)?;
+    endorser_rep.endorsements_given = endorser_rep.endorsements_given.saturating_sub(1);
+    endorser_rep.score = sub_score(
+        &endorser_rep,
+        score_points(ENDORSER_POINTS),
+        "revoke_endorsement",
+    )?;
+    endorser_rep.last_updated = env.block.time.seconds();
+    events.extend(save_reputation(storage, env, &endorser_rep)?);
+    events.extend(evaluate_badges(storage, env, &endorsement.endorsee)?);
+
+    Ok(events)
+}
+
+// Persist a reputation record. Returns a `tier_changed` event when an update moves the
+// user to another tier, with "none" for a score below every threshold; the first save
+// at sign-up is not a change.
+fn save_reputation(
+    storage: &mut dyn Storage,
+    env: &Env,
+    reputation: &UserReputation,
+) -> StdResult<Vec<Event>> {
+    let previous = REPUTATIONS.may_load(storage, &reputation.user)?;
+    REPUTATIONS.save(storage, &reputation.user, reputation, env.block.height)?;
+    REPUTATION_ACCOUNTS.save(storage, &reputation.user, &Empty {})?;
+    if let Some(previous) = &previous {
+        unindex_reputation(storage, previous)?;
+    }
+    index_reputation(storage, reputation)?;
+    count_reputation(storage, previous.as_ref(), Some(reputation))?;
+
+    let mut events = vec![];
+    let tiers = REPUTATION_TIERS.may_load(storage)?.unwrap_or_default();
+    let new_tier = tier_for_score(&tiers, reputation.score);
+    let old_tier = previous
+        .map(|previous| tier_for_score(&tiers, previous.score))
+        .filter(|old_tier| *old_tier != new_tier);
+    if let Some(old_tier) = old_tier {
+        let none = || "none".to_string();
+        events.push(
+            Event::new("tier_changed")
+                .add_attribute("user", reputation.user.as_str())
+                .add_attribute("old_tier", old_tier.unwrap_or_else(none))
+                .add_attribute("new_tier", new_tier.unwrap_or_else(none))
+                .add_attribute("score", reputation.score.to_string()),
+        );
+    }
+    events.extend(award_achievements(storage, env, reputation)?);
+
+    Ok(events)
+}
+
+// Add an account to the global and per-platform leaderboards it belongs on
+fn index_reputation(storage: &mut dyn Storage, reputation: &UserReputation) -> StdResult<()> {
+    if reputation.platforms.is_empty() {
+        return Ok(());
+    }
+    let key = score_key(reputation.score);
+    GLOBAL_SCORE_INDEX.save(storage, (key, &reputation.user), &Empty {})?;
+    GLOBAL_LEADERBOARD_SIZE.update(storage, |size| -> StdResult<_> { Ok(size + 1) })?;
+    for platform in &reputation.platforms {
+        PLATFORM_SCORE_INDEX.save(storage, (platform, key, &reputation.user), &Empty {})?;
+        PLATFORM_LEADERBOARD_SIZES.update(storage, platform, |size| -> StdResult<_> {
+            Ok(size.unwrap_or_default() + 1)
+        })?;
+    }
+    Ok(())
+}
+
+// Undo `index_reputation` for a stored record
+fn unindex_reputation(storage: &mut dyn Storage, reputation: &UserReputation) -> StdResult<()> {
+    if reputation.platforms.is_empty() {
+        return Ok(());
+    }
+    let key = score_key(reputation.score);
+    GLOBAL_SCORE_INDEX.remove(storage, (key, &reputation.user));
+    GLOBAL_LEADERBOARD_SIZE.update(storage, |size| -> StdResult<_> {
+        Ok(size.saturating_sub(1))
+    })?;
+    for platform in &reputation.platforms {
+        PLATFORM_SCORE_INDEX.remove(storage, (platform, key, &reputation.user));
+        PLATFORM_LEADERBOARD_SIZES.update(storage, platform, |size| -> StdResult<_> {
+            Ok(size.unwrap_or_default().saturating_sub(1))
+        })?;
+    }
+    Ok(())
+}
+
+// Move an account's score in the prefix counts of each leaderboard it is on, from its
+// stored record to its new one; `None` on either side adds or removes the account
+fn count_reputation(
+    storage: &mut dyn Storage,
+    previous: Option<&UserReputation>,
+    reputation: Option<&UserReputation>,
+) -> StdResult<()> {
+    let previous = previous.filter(|previous| !previous.platforms.is_empty());
+    let reputation = reputation.filter(|reputation| !reputation.platforms.is_empty());
+    let old_key = previous.map(|previous| score_key(previous.score));
+    let new_key = reputation.map(|reputation| score_key(reputation.score));
+    count_score(storage, None, old_key, new_key)?;
+
+    let mut platforms = previous
+        .into_iter()
+        .chain(reputation)
+        .flat_map(|reputation| reputation.platforms.iter())
+        .collect::<Vec<_>>();
+    platforms.sort();
+    platforms.dedup();
+    for platform in platforms {
+        let on = |reputation: Option<&UserReputation>| {
+            reputation
+                .filter(|reputation| reputation.platforms.contains(platform))
+                .map(|reputation| score_key(reputation.score))
+        };
+        count_score(storage, Some(platform), on(previous), on(reputation))?;
+    }
+    Ok(())
+}
+
+// Move one account from `old_key` to `new_key` in the prefix counts of the global
+// leaderboard, or of a platform's. Levels where both keys share a prefix are left as is.
+fn count_score(
+    storage: &mut dyn Storage,
+    platform: Option<&str>,
+    old_key: Option<u128>,
+    new_key: Option<u128>,
+) -> StdResult<()> {
+    for level in 1..=SCORE_PREFIX_LEVELS {
+        let old_prefix = old_key.map(|key| score_prefix(key, level));
+        let new_prefix = new_key.map(|key| score_prefix(key, level));
+        if old_prefix == new_prefix {
+            continue;
+        }
+        for (prefix, added) in [(old_prefix, false), (new_prefix, true)] {
+            let Some(prefix) = prefix else {
+                continue;
+            };
+            match platform {
+                Some(platform) => step_count(
+                    storage,
+                    &PLATFORM_SCORE_COUNTS,
+                    (platform, level, prefix),
+                    added,
+                )?,
+                None => step_count(storage, &GLOBAL_SCORE_COUNTS, (level, prefix), added)?,
+            }
+        }
+    }
+    Ok(())
+}
+
+// Move a counter up or down by one, dropping it at zero so ranges only see scores held
+fn step_count<'a, K: PrimaryKey<'a> + Clone>(
+    storage: &mut dyn Storage,
+    counts: &Map<'a, K, u64>,
+    key: K,
+    added: bool,
+) -> StdResult<()> {
+    let count = counts.may_load(storage, key.clone())?.unwrap_or_default();
+    let count = if added {
+        count + 1
+    } else {
+        count.saturating_sub(1)
+    };
+    if count == 0 {
+        counts.remove(storage, key);
+        Ok(())
+    } else {
+        counts.save(storage, key, &count)
+    }
+}
+
+// Unlock every achievement whose threshold the reputation's counters have reached
+fn award_achievements(
+    storage: &mut dyn Storage,
+    env: &Env,
+    reputation: &UserReputation,
+) -> StdResult<Vec<Event>> {
+    let achievements = ACHIEVEMENTS
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, achievement)| achievement))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut events = vec![];
+    for achievement in achievements {
+        let value = reputation.counter(achievement.metric);
+        let key = (&reputation.user, achievement.id.as_str());
+        if value < achievement.threshold || USER_ACHIEVEMENTS.has(storage, key) {
+            continue;
+        }
+        USER_ACHIEVEMENTS.save(
+            storage,
+            key,
+            &AchievementAward {
+                achievement_id: achievement.id.clone(),
+                user: reputation.user.clone(),
+                value,
+                awarded_at: env.block.time.seconds(),
+                height: env.block.height,
+            },
+        )?;
+        events.push(
+            Event::new("achievement_unlocked")
+                .add_attribute("user", reputation.user.as_str())
+                .add_attribute("achievement", achievement.id)
+                .add_attribute("value", value.to_string()),
+        );
+    }
+    Ok(events)
+}
+
+// Reputation points as a fixed-point score
+fn score_points(points: i64) -> SignedDecimal {
+    SignedDecimal::from_ratio(points, 1)
+}
+
+// Unsigned points as a score delta, failing with the operation and account when too large
+fn to_score_points(
+    points: Decimal,
+    operation: &str,
+    account: &Addr,
+) -> Result<SignedDecimal, ContractError> {
+    SignedDecimal::try_from(points).map_err(|_| score_overflow(operation, account))
+}
+
+// An endorsement's effective weight, failing with the operation and endorsee on overflow
+fn effective_weight(endorsement: &Endorsement, operation: &str) -> Result<Decimal, ContractError> {
+    endorsement
+        .effective_weight()
+        .map_err(|_| score_overflow(operation, &endorsement.endorsee))
+}
+
+// An endorsement's effective weight as score points
+fn weight_points(
+    endorsement: &Endorsement,
+    operation: &str,
+) -> Result<SignedDecimal, ContractError> {
+    to_score_points(
+        effective_weight(endorsement, operation)?,
+        operation,
+        &endorsement.endorsee,
+    )
+}
+
+// The reputation's score raised by `delta`, failing with the operation and account on overflow
+fn add_score(
+    reputation: &UserReputation,
+    delta: SignedDecimal,
+    operation: &str,
+) -> Result<SignedDecimal, ContractError> {
+    reputation
+        .score
+        .checked_add(delta)
+        .map_err(|_| score_overflow(operation, &reputation.user))
+}
+
+fn sub_score(
+    reputation: &UserReputation,
+    delta: SignedDecimal,
+    operation: &str,
+) -> Result<SignedDecimal, ContractError> {
+    reputation
+        .score
+        .checked_sub(delta)
+        .map_err(|_| score_overflow(operation, &reputation.user))
+}
+
+fn score_overflow(operation: &str, account: &Addr) -> ContractError {
+    ContractError::ScoreOverflow {
+        operation: operation.to_string(),
+        account: account.to_string(),
+    }
+}
+
+// Highest tier whose threshold the score reaches
+fn tier_for_score(tiers: &[ReputationTier], score: SignedDecimal) -> Option<String> {
+    tiers
+        .iter()
+        .rev()
+        .find(|tier| score >= tier.min_score)
+        .map(|tier| tier.name.clone())
+}
+
+fn default_tiers() -> Vec<ReputationTier> {
+    DEFAULT_TIERS
+        .iter()
+        .map(|&(name, min_score)| ReputationTier {
+            name: name.to_string(),
+            min_score: score_points(min_score),
+        })
+        .collect()
+}
+
+// Whether the endorser holds active verified proofs or live NFTs on a platform related to
+// the skill. An NFT minted by hand without proofs counts; one whose proofs all lapsed does
+// not. Returns None when the skill has no platform mapping.
+fn is_qualified_endorser(
+    storage: &dyn Storage,
+    env: &Env,
+    endorser: &Addr,
+    skill: &str,
+) -> StdResult<Option<bool>> {
+    let platforms = match SKILL_PLATFORMS.may_load(storage, skill)? {
+        Some(platforms) => platforms,
+        None => return Ok(None),
+    };
+    let now = env.block.time.seconds();
+
+    for proof_id in USER_PROOFS.may_load(storage, endorser)?.unwrap_or_default() {
+        let proof = PROOFS.load(storage, proof_id)?;
+        if platforms.contains(&proof.platform) && proof.verified && proof.is_active(now) {
+            return Ok(Some(true));
+        }
+    }
+
+    let token_ids = USER_NFTS.may_load(storage, endorser)?.unwrap_or_default();
+    for token_id in token_ids {
+        if let Some(nft) = SKILL_NFTS.may_load(storage, token_id)? {
+            if !platforms.contains(&nft.platform) {
+                continue;
+            }
+            let backed = !nft.proof_ids.is_empty();
+            if !backed || !live_skill_nft(storage, nft, now)?.proof_ids.is_empty() {
+                return Ok(Some(true));
+            }
+        }
+    }
+
+    Ok(Some(false))
+}
+
+// Look for a ring that the new endorser -> endorsee edge would close. Returns the kind
+// of ring and any accounts in it besides the endorser and endorsee.
+fn detect_collusion(
+    storage: &dyn Storage,
+    endorser: &Addr,
+    endorsee: &Addr,
+) -> StdResult<Option<(CollusionKind, Vec<Addr>)>> {
+    if has_active_endorsement(storage, endorsee, endorser)? {
+        return Ok(Some((CollusionKind::Reciprocal, vec![])));
+    }
+
+    // endorsee -> middle -> endorser, over the accounts the endorsee actively endorses.
+    // An endorsee with more of them than the scan covers cannot be cleared.
+    let middles = ACTIVE_ENDORSEMENT_EDGES
+        .prefix(endorsee)
+        .keys(storage, None, None, Order::Ascending)
+        .take(MAX_RING_SCAN + 1)
+        .collect::<StdResult<Vec<_>>>()?;
+    for middle in middles.iter().take(MAX_RING_SCAN) {
+        if has_active_endorsement(storage, middle, endorser)? {
+            return Ok(Some((CollusionKind::Cycle, vec![middle.clone()])));
+        }
+    }
+    if middles.len() > MAX_RING_SCAN {
+        return Ok(Some((CollusionKind::Unchecked, vec![])));
+    }
+
+    Ok(None)
+}
+
+// Whether `from` currently endorses `to` for any skill
+fn has_active_endorsement(storage: &dyn Storage, from: &Addr, to: &Addr) -> StdResult<bool> {
+    Ok(ACTIVE_ENDORSEMENT_EDGES.has(storage, (from, to)))
+}
+
+fn flag_collusion(
+    storage: &mut dyn Storage,
+    env: &Env,
+    user: &Addr,
+    kind: CollusionKind,
+    counterparties: Vec<Addr>,
+) -> StdResult<()> {
+    let now = env.block.time.seconds();
+    let mut flag = COLLUSION_FLAGS
+        .may_load(storage, user)?
+        .unwrap_or_else(|| CollusionFlag {
+            user: user.clone(),
+            kinds: vec![],
+            counterparties: vec![],
+            occurrences: 0,
+            first_flagged: now,
+            last_flagged: now,
+        });
+
+    if !flag.kinds.contains(&kind) {
+        flag.kinds.push(kind);
+    }
+    for counterparty in counterparties {
+        if !flag.counterparties.contains(&counterparty) {
+            flag.counterparties.push(counterparty);
+        }
+    }
+    flag.occurrences += 1;
+    flag.last_flagged = now;
+
+    COLLUSION_FLAGS.save(storage, user, &flag)
+}
+
+pub fn execute_set_reputation_tiers(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    tiers: Vec<ReputationTier>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if tiers.is_empty() {
+        return Err(ContractError::InvalidTiers {
+            reason: "at least one tier is required".to_string(),
+        });
+    }
+    if tiers.iter().any(|tier| tier.name.trim().is_empty()) {
+        return Err(ContractError::InvalidTiers {
+            reason: "tier names must not be empty".to_string(),
+        });
+    }
+    if tiers
+        .windows(2)
+        .any(|pair| pair[0].min_score >= pair[1].min_score)
+    {
+        return Err(ContractError::InvalidTiers {
+            reason: "thresholds must be strictly ascending".to_string(),
+        });
+    }
+
+    REPUTATION_TIERS.save(deps.storage, &tiers, env.block.height)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "set_reputation_tiers")
+        .add_attribute("tiers", tiers.len().to_string()))
+}
+
+pub fn execute_set_skill_platforms(
+    deps: DepsMut,
+    info: MessageInfo,
+    skill: String,
+    platforms: Vec<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for platform in &platforms {
+        if !config.supported_platforms.contains(platform) {
+            return Err(ContractError::UnsupportedPlatform {
+                platform: platform.clone(),
+            });
+        }
+    }
+
+    if platforms.is_empty() {
+        SKILL_PLATFORMS.remove(deps.storage, &skill);
+    } else {
+        SKILL_PLATFORMS.save(deps.storage, &skill, &platforms)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "set_skill_platforms")
+        .add_attribute("skill", skill)
+        .add_attribute("platforms", platforms.join(",")))
+}
+
+pub fn execute_set_level_rules(
+    deps: DepsMut,
+    info: MessageInfo,
+    platform: String,
+    rules: Vec<LevelRule>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if !config.supported_platforms.contains(&platform) {
+        return Err(ContractError::UnsupportedPlatform { platform });
+    }
+
+    for rule in &rules {
+        if rule.thresholds.is_empty() || rule.thresholds.len() > 4 {
+            return Err(ContractError::InvalidLevelRules {
+                reason: "each rule needs between 1 and 4 thresholds".to_string(),
+            });
+        }
+        if rule.thresholds.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(ContractError::InvalidLevelRules {
+                reason: "thresholds must be strictly ascending".to_string(),
+            });
+        }
+    }
+
+    if rules.is_empty() {
+        LEVEL_RULES.remove(deps.storage, &platform);
+    } else {
+        LEVEL_RULES.save(deps.storage, &platform, &rules)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "set_level_rules")
+        .add_attribute("platform", platform)
+        .add_attribute("rules", rules.len().to_string()))
+}
+
+pub fn execute_set_svg_template(
+    deps: DepsMut,
+    info: MessageInfo,
+    platform: String,
+    level: Option<u32>,
+    template: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if !config.supported_platforms.contains(&platform) {
+        return Err(ContractError::UnsupportedPlatform { platform });
+    }
+
+    let level = level.unwrap_or(0);
+    if level > 4 {
+        return Err(ContractError::InvalidSkillLevel { level });
+    }
+
+    match &template {
+        Some(template) => {
+            if template.len() > MAX_SVG_TEMPLATE_BYTES {
+                return Err(ContractError::InvalidSvgTemplate {
+                    reason: format!("must not exceed {} bytes", MAX_SVG_TEMPLATE_BYTES),
+                });
+            }
+            if !template.trim_start().starts_with("<svg")
+                || !template.trim_end().ends_with("</svg>")
+            {
+                return Err(ContractError::InvalidSvgTemplate {
+                    reason: "must be a single <svg> element".to_string(),
+                });
+            }
+            SVG_TEMPLATES.save(deps.storage, (&platform, level), template)?;
+        }
+        None => SVG_TEMPLATES.remove(deps.storage, (&platform, level)),
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "set_svg_template")
+        .add_attribute("platform", platform)
+        .add_attribute("level", level.to_string())
+        .add_attribute("removed", template.is_none().to_string()))
+}
+
+pub fn execute_set_badge_rule(
+    deps: DepsMut,
+    info: MessageInfo,
+    rule: BadgeRule,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if !is_valid_rule_id(&rule.id) {
+        return Err(ContractError::InvalidBadgeRule {
+            reason: "id must be 1-32 characters of a-z, 0-9, '-' or '_'".to_string(),
+        });
+    }
+    if rule.name.trim().is_empty() {
+        return Err(ContractError::InvalidBadgeRule {
+            reason: "name must not be empty".to_string(),
+        });
+    }
+    if validate_badge_condition(&config, &rule.condition, 1)? > MAX_BADGE_CONDITIONS {
+        return Err(ContractError::InvalidBadgeRule {
+            reason: format!("at most {} conditions per rule", MAX_BADGE_CONDITIONS),
+        });
+    }
+    if !BADGE_RULES.has(deps.storage, &rule.id)
+        && BADGE_RULES
+            .keys(deps.storage, None, None, Order::Ascending)
+            .count()
+            >= MAX_BADGE_RULES
+    {
+        return Err(ContractError::InvalidBadgeRule {
+            reason: format!("at most {} badge rules", MAX_BADGE_RULES),
+        });
+    }
+
+    BADGE_RULES.save(deps.storage, &rule.id, &rule)?;
+
+    // Accounts are only evaluated against the rule on their next refresh
+    Ok(Response::new()
+        .add_attribute("method", "set_badge_rule")
+        .add_attribute("badge", rule.id)
+        .add_attribute("evaluation", "deferred"))
+}
+
+// Ids of badge rules and achievements: 1-32 characters of a-z, 0-9, '-' or '_'
+fn is_valid_rule_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= 32
+        && id
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+}
+
+// Check a condition tree, returning the number of conditions in it
+fn validate_badge_condition(
+    config: &Config,
+    condition: &BadgeCondition,
+    depth: u32,
+) -> Result<usize, ContractError> {
+    if depth > MAX_BADGE_CONDITION_DEPTH {
+        return Err(ContractError::InvalidBadgeRule {
+            reason: format!(
+                "conditions nest at most {} levels",
+                MAX_BADGE_CONDITION_DEPTH
+            ),
+        });
+    }
+    match condition {
+        BadgeCondition::NftLevel {
+            platform,
+            min_level,
+        } => {
+            if !config.supported_platforms.contains(platform) {
+                return Err(ContractError::UnsupportedPlatform {
+                    platform: platform.clone(),
+                });
+            }
+            if !(1..=4).contains(min_level) {
+                return Err(ContractError::InvalidSkillLevel { level: *min_level });
+            }
+            Ok(1)
+        }
+        BadgeCondition::Proofs { platform, count } => {
+            if !config.supported_platforms.contains(platform) {
+                return Err(ContractError::UnsupportedPlatform {
+                    platform: platform.clone(),
+                });
+            }
+            if *count == 0 {
+                return Err(ContractError::InvalidBadgeRule {
+                    reason: "proof count must be at least 1".to_string(),
+                });
+            }
+            Ok(1)
+        }
+        BadgeCondition::Endorsements { skill, count } => {
+            if skill.trim().is_empty() || *count == 0 {
+                return Err(ContractError::InvalidBadgeRule {
+                    reason: "endorsements need a skill and a count of at least 1".to_string(),
+                });
+            }
+            Ok(1)
+        }
+        BadgeCondition::AllOf { conditions } | BadgeCondition::AnyOf { conditions } => {
+            if conditions.is_empty() {
+                return Err(ContractError::InvalidBadgeRule {
+                    reason: "all_of and any_of need at least one condition".to_string(),
+                });
+            }
+            let mut total = 1;
+            for condition in conditions {
+                total += validate_badge_condition(config, condition, depth + 1)?;
+            }
+            Ok(total)
+        }
+    }
+}
+
+pub fn execute_remove_badge_rule(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    if !BADGE_RULES.has(deps.storage, &id) {
+        return Err(ContractError::BadgeRuleNotFound { id });
+    }
+
+    BADGE_RULES.remove(deps.storage, &id);
+
+    Ok(Response::new()
+        .add_attribute("method", "remove_badge_rule")
+        .add_attribute("badge", id))
+}
+
+pub fn execute_refresh_badges(
+    deps: DepsMut,
+    env: Env,
+    user: String,
+) -> Result<Response, ContractError> {
+    let user_addr = deps.api.addr_validate(&user)?;
+    let events = evaluate_badges(deps.storage, &env, &user_addr)?;
+
+    Ok(Response::new()
+        .add_events(events)
+        .add_attribute("method", "refresh_badges")
+        .add_attribute("user", user_addr))
+}
+
+// Award the user every composite badge they now qualify for, and revoke the ones whose
+// conditions no longer hold
+fn evaluate_badges(storage: &mut dyn Storage, env: &Env, user: &Addr) -> StdResult<Vec<Event>> {
+    let rules = BADGE_RULES
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, rule)| rule))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut events = vec![];
+    for rule in rules {
+        let platform = format!("{}{}", BADGE_PLATFORM_PREFIX, rule.id);
+        let held = OWNER_PLATFORM_NFTS.may_load(storage, (user, &platform))?;
+        if held.is_none()
+            && (BURNED_NFTS.has(storage, (user, &platform))
+                || REVOKED_NFTS.has(storage, (user, &platform)))
+        {
+            continue;
+        }
+        match (
+            badge_condition_met(storage, env, user, &rule.condition)?,
+            held,
+        ) {
+            (true, None) => {
+                let (token_id, _) = save_skill_nft(
+                    storage,
+                    env,
+                    user,
+                    &platform,
+                    BADGE_LEVEL,
+                    None,
+                    vec![],
+                    &env.contract.address,
+                )?;
+                events.push(
+                    Event::new("badge_awarded")
+                        .add_attribute("token_id", token_id)
+                        .add_attribute("owner", user.as_str())
+                        .add_attribute("badge", &rule.id),
+                );
+            }
+            (false, Some(token_id)) => {
+                let nft = SKILL_NFTS.load(storage, token_id)?;
+                remove_skill_nft(storage, env, &nft, vec![])?;
+                events.push(skill_nft_removed_event(
+                    "skill_nft_revoked",
+                    &nft,
+                    Some("badge_requirements_unmet"),
+                ));
+            }
+            _ => {}
+        }
+    }
+    Ok(events)
+}
+
+fn badge_condition_met(
+    storage: &dyn Storage,
+    env: &Env,
+    user: &Addr,
+    condition: &BadgeCondition,
+) -> StdResult<bool> {
+    match condition {
+        BadgeCondition::NftLevel {
+            platform,
+            min_level,
+        } => Ok(held_level(storage, user, platform, env.block.time.seconds())? >= *min_level),
+        BadgeCondition::Proofs { platform, count } => {
+            let proof_ids = platform_proof_ids(storage, user, platform, env.block.time.seconds())?;
+            Ok(proof_ids.len() >= *count as usize)
+        }
+        BadgeCondition::Endorsements { skill, count } => {
+            let mut matching = 0;
+            for endorsement_id in USER_ENDORSEMENTS
+                .may_load(storage, user)?
+                .unwrap_or_default()
+            {
+                if ENDORSEMENTS.load(storage, endorsement_id)?.skill == *skill {
+                    matching += 1;
+                }
+            }
+            Ok(matching >= *count)
+        }
+        BadgeCondition::AllOf { conditions } => {
+            for condition in conditions {
+                if !badge_condition_met(storage, env, user, condition)? {
+                    return Ok(false);
+                }
+            }
+            Ok(true)
+        }
+        BadgeCondition::AnyOf { conditions } => {
+            for condition in conditions {
+                if badge_condition_met(storage, env, user, condition)? {
+                    return Ok(true);
+                }
+            }
+            Ok(false)
+        }
+    }
+}
+
+// Rule behind a composite badge NFT; `None` for platform NFTs and removed rules
+fn badge_rule_for(storage: &dyn Storage, nft: &SkillNft) -> StdResult<Option<BadgeRule>> {
+    match nft.platform.strip_prefix(BADGE_PLATFORM_PREFIX) {
+        Some(id) => BADGE_RULES.may_load(storage, id),
+        None => Ok(None),
+    }
+}
+
+pub fn execute_set_achievement(
+    deps: DepsMut,
+    info: MessageInfo,
+    achievement: Achievement,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if !is_valid_rule_id(&achievement.id) {
+        return Err(ContractError::InvalidAchievement {
+            reason: "id must be 1-32 characters of a-z, 0-9, '-' or '_'".to_string(),
+        });
+    }
+    if achievement.name.trim().is_empty() {
+        return Err(ContractError::InvalidAchievement {
+            reason: "name must not be empty".to_string(),
+        });
+    }
+    if achievement.threshold == 0 {
+        return Err(ContractError::InvalidAchievement {
+            reason: "threshold must be at least 1".to_string(),
+        });
+    }
+    if !ACHIEVEMENTS.has(deps.storage, &achievement.id)
+        && ACHIEVEMENTS
+            .keys(deps.storage, None, None, Order::Ascending)
+            .count()
+            >= MAX_ACHIEVEMENTS
+    {
+        return Err(ContractError::InvalidAchievement {
+            reason: format!("at most {} achievements", MAX_ACHIEVEMENTS),
+        });
+    }
+
+    ACHIEVEMENTS.save(deps.storage, &achievement.id, &achievement)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "set_achievement")
+        .add_attribute("achievement", achievement.id))
+}
+
+pub fn execute_remove_achievement(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    if !ACHIEVEMENTS.has(deps.storage, &id) {
+        return Err(ContractError::AchievementNotFound { id });
+    }
+
+    ACHIEVEMENTS.remove(deps.storage, &id);
+
+    Ok(Response::new()
+        .add_attribute("method", "remove_achievement")
+        .add_attribute("achievement", id))
+}
+
+pub fn execute_clear_collusion_flag(
+    deps: DepsMut,
+    info: MessageInfo,
+    user: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let user_addr = deps.api.addr_validate(&user)?;
+    COLLUSION_FLAGS.remove(deps.storage, &user_addr);
+
+    Ok(Response::new()
+        .add_attribute("method", "clear_collusion_flag")
+        .add_attribute("user", user_addr))
+}
+
+pub fn execute_request_endorsement(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    endorser: String,
+    skill: String,
+    message: Option<String>,
+) -> Result<Response, ContractError> {
+    let endorser_addr = deps.api.addr_validate(&endorser)?;
+
+    // Users cannot ask themselves
+    if info.sender == endorser_addr {
+        return Err(ContractError::SelfEndorsement {});
+    }
+
+    let request_key = (&endorser_addr, &info.sender, skill.as_str());
+    if OPEN_ENDORSEMENT_REQUESTS.has(deps.storage, request_key) {
+        return Err(ContractError::DuplicateEndorsementRequest {});
+    }
+
+    // Nothing to ask for if the endorsement is already in place
+    let pair_key = (&endorser_addr, &info.sender, skill.as_str());
+    if let Some(endorsement_id) = ENDORSEMENT_PAIRS.may_load(deps.storage, pair_key)? {
+        let endorsement = ENDORSEMENTS.load(deps.storage, endorsement_id)?;
+        if matches!(
+            endorsement.status,
+            EndorsementStatus::Active | EndorsementStatus::Pending
+        ) {
+            return Err(ContractError::DuplicateEndorsement {});
+        }
+    }
+
+    let mut sent = REQUESTS_SENT
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    if sent.len() >= MAX_OPEN_ENDORSEMENT_REQUESTS as usize {
+        return Err(ContractError::TooManyEndorsementRequests {
+            limit: MAX_OPEN_ENDORSEMENT_REQUESTS,
+        });
+    }
+
+    let mut received = REQUESTS_RECEIVED
+        .may_load(deps.storage, &endorser_addr)?
+        .unwrap_or_default();
+    if received.len() >= MAX_RECEIVED_ENDORSEMENT_REQUESTS as usize {
+        return Err(ContractError::EndorserRequestsFull {
+            limit: MAX_RECEIVED_ENDORSEMENT_REQUESTS,
+        });
+    }
+
+    // Generate request ID
+    let request_id = format!(
+        "{}:{}:{}:{}",
+        info.sender,
+        endorser_addr,
+        skill,
+        env.block.time.seconds()
+    );
+
+    let request = EndorsementRequest {
+        id: request_id.clone(),
+        requester: info.sender.clone(),
+        endorser: endorser_addr.clone(),
+        skill: skill.clone(),
+        message,
+        created_at: env.block.time.seconds(),
+        status: EndorsementRequestStatus::Open,
+        endorsement_id: None,
+    };
+
+    ENDORSEMENT_REQUESTS.save(deps.storage, request_id.clone(), &request)?;
+    OPEN_ENDORSEMENT_REQUESTS.save(deps.storage, request_key, &request_id)?;
+
+    // Index the open request on both sides
+    sent.push(request_id.clone());
+    REQUESTS_SENT.save(deps.storage, &info.sender, &sent)?;
+    received.push(request_id.clone());
+    REQUESTS_RECEIVED.save(deps.storage, &endorser_addr, &received)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "request_endorsement")
+        .add_attribute("request_id", request_id)
+        .add_attribute("requester", info.sender)
+        .add_attribute("endorser", endorser_addr)
+        .add_attribute("skill", skill))
+}
+
+// Close an open request once the endorser has responded with an endorsement
+fn fulfill_endorsement_request(
+    storage: &mut dyn Storage,
+    request_id: &str,
+    endorsement_id: &str,
+) -> StdResult<()> {
+    let mut request = ENDORSEMENT_REQUESTS.load(storage, request_id.to_string())?;
+    request.status = EndorsementRequestStatus::Fulfilled;
+    request.endorsement_id = Some(endorsement_id.to_string());
+    close_endorsement_request(storage, &request)
+}
+
+pub fn execute_dismiss_endorsement_request(
+    deps: DepsMut,
+    info: MessageInfo,
+    request_id: String,
+) -> Result<Response, ContractError> {
+    let mut request = ENDORSEMENT_REQUESTS
+        .may_load(deps.storage, request_id.clone())?
+        .ok_or_else(|| ContractError::EndorsementRequestNotFound {
+            request_id: request_id.clone(),
+        })?;
+    if info.sender != request.endorser {
+        return Err(ContractError::Unauthorized {});
+    }
+    if request.status != EndorsementRequestStatus::Open {
+        return Err(ContractError::EndorsementRequestClosed { request_id });
+    }
+
+    request.status = EndorsementRequestStatus::Dismissed;
+    close_endorsement_request(deps.storage, &request)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "dismiss_endorsement_request")
+        .add_attribute("request_id", request_id)
+        .add_attribute("requester", request.requester)
+        .add_attribute("endorser", request.endorser)
+        .add_attribute("skill", request.skill))
+}
+
+// Save a request that is no longer open and drop it from the open-request indexes
+fn close_endorsement_request(
+    storage: &mut dyn Storage,
+    request: &EndorsementRequest,
+) -> StdResult<()> {
+    ENDORSEMENT_REQUESTS.save(storage, request.id.clone(), request)?;
+
+    OPEN_ENDORSEMENT_REQUESTS.remove(
+        storage,
+        (
+            &request.endorser,
+            &request.requester,
+            request.skill.as_str(),
+        ),
+    );
+    for (index, user) in [
+        (REQUESTS_SENT, &request.requester),
+        (REQUESTS_RECEIVED, &request.endorser),
+    ] {
+        index.update(storage, user, |ids| -> StdResult<_> {
+            let mut ids = ids.unwrap_or_default();
+            ids.retain(|id| *id != request.id);
+            Ok(ids)
+        })?;
+    }
+
+    Ok(())
+}
+
+pub fn execute_accept_endorsement(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    endorsement_id: String,
+) -> Result<Response, ContractError> {
+    let mut endorsement = load_pending_endorsement(deps.storage, &info.sender, &endorsement_id)?;
+    let config = CONFIG.load(deps.storage)?;
+
+    // The ring check runs against the edges active now, not when the endorsement was made
+    let (collusion, discount) = check_collusion(
+        deps.storage,
+        &env,
+        &config,
+        &endorsement.endorser,
+        &endorsement.endorsee,
+    )?;
+    endorsement.discount = discount;
+    endorsement.status = EndorsementStatus::Active;
+    endorsement.updated_at = Some(env.block.time.seconds());
+    ENDORSEMENTS.save(deps.storage, endorsement_id.clone(), &endorsement)?;
+    remove_pending_endorsement(deps.storage, &endorsement)?;
+
+    let events = apply_endorsement(deps.storage, &env, &endorsement)?;
+
+    Ok(Response::new()
+        .add_events(events)
+        .add_attribute("method", "accept_endorsement")
+        .add_attribute("endorsement_id", endorsement_id)
+        .add_attribute("endorser", endorsement.endorser)
+        .add_attribute("endorsee", endorsement.endorsee)
+        .add_attribute("collusion", collusion_name(collusion)))
+}
+
+pub fn execute_decline_endorsement(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    endorsement_id: String,
+) -> Result<Response, ContractError> {
+    let mut endorsement = load_pending_endorsement(deps.storage, &info.sender, &endorsement_id)?;
+
+    endorsement.status = EndorsementStatus::Declined;
+    endorsement.updated_at = Some(env.block.time.seconds());
+    ENDORSEMENTS.save(deps.storage, endorsement_id.clone(), &endorsement)?;
+    remove_pending_endorsement(deps.storage, &endorsement)?;
+    USER_DECLINED_ENDORSEMENTS.update(
+        deps.storage,
+        &endorsement.endorsee,
+        |ids| -> StdResult<_> {
+            let mut ids = ids.unwrap_or_default();
+            ids.push(endorsement_id.clone());
+            Ok(ids)
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("method", "decline_endorsement")
+        .add_attribute("endorsement_id", endorsement_id)
+        .add_attribute("endorser", endorsement.endorser)
+        .add_attribute("endorsee", endorsement.endorsee))
+}
+
+fn load_pending_endorsement(
+    storage: &dyn Storage,
+    endorsee: &Addr,
+    endorsement_id: &str,
+) -> Result<Endorsement, ContractError> {
+    let endorsement = ENDORSEMENTS
+        .may_load(storage, endorsement_id.to_string())?
+        .ok_or_else(|| ContractError::EndorsementNotFound {
+            endorsement_id: endorsement_id.to_string(),
+        })?;
+
+    // Only the endorsee decides on a pending endorsement
+    if endorsee != endorsement.endorsee {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if endorsement.status != EndorsementStatus::Pending {
+        return Err(ContractError::EndorsementNotPending {
+            endorsement_id: endorsement_id.to_string(),
+        });
+    }
+
+    Ok(endorsement)
+}
+
+fn remove_pending_endorsement(
+    storage: &mut dyn Storage,
+    endorsement: &Endorsement,
+) -> StdResult<()> {
+    PENDING_ENDORSEMENTS.update(storage, &endorsement.endorsee, |ids| -> StdResult<_> {
+        let mut ids = ids.unwrap_or_default();
+        ids.retain(|id| id != &endorsement.id);
+        Ok(ids)
+    })?;
+    Ok(())
+}
+
+pub fn execute_revoke_endorsement(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    endorsement_id: String,
+) -> Result<Response, ContractError> {
+    let mut endorsement = load_active_endorsement(deps.storage, &endorsement_id)?;
+
+    // Only the endorser can withdraw an endorsement
+    if info.sender != endorsement.endorser {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // A pending endorsement never counted, so it only leaves the endorsee's pending list
+    if endorsement.status == EndorsementStatus::Pending {
+        remove_pending_endorsement(deps.storage, &endorsement)?;
+        endorsement.status = EndorsementStatus::Withdrawn;
+        endorsement.updated_at = Some(env.block.time.seconds());
+        ENDORSEMENTS.save(deps.storage, endorsement_id.clone(), &endorsement)?;
+        USER_WITHDRAWN_ENDORSEMENTS.update(
+            deps.storage,
+            &endorsement.endorsee,
+            |ids| -> StdResult<_> {
+                let mut ids = ids.unwrap_or_default();
+                ids.push(endorsement_id.clone());
+                Ok(ids)
+            },
+        )?;
+
+        return Ok(Response::new()
+            .add_attribute("method", "revoke_endorsement")
+            .add_attribute("endorsement_id", endorsement_id)
+            .add_attribute("endorser", endorsement.endorser)
+            .add_attribute("endorsee", endorsement.endorsee)
+            .add_attribute("status", "withdrawn"));
+    }
+
+    let events = reverse_endorsement(deps.storage, &env, &endorsement)?;
+    endorsement.status = EndorsementStatus::Revoked;
+    endorsement.updated_at = Some(env.block.time.seconds());
+    ENDORSEMENTS.save(deps.storage, endorsement_id.clone(), &endorsement)?;
+
+    // Keep revoked endorsements visible on the endorsee's profile
+    USER_REVOKED_ENDORSEMENTS.update(
+        deps.storage,
+        &endorsement.endorsee,
+        |ids| -> StdResult<_> {
+            let mut ids = ids.unwrap_or_default();
+            ids.push(endorsement_id.clone());
+            Ok(ids)
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_events(events)
+        .add_attribute("method", "revoke_endorsement")
+        .add_attribute("endorsement_id", endorsement_id)
+        .add_attribute("endorser", endorsement.endorser)
+        .add_attribute("endorsee", endorsement.endorsee)
+        .add_attribute("status", "revoked"))
+}
+
+pub fn execute_update_endorsement(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    endorsement_id: String,
+    message: Option<String>,
+    weight: Option<u32>,
+) -> Result<Response, ContractError> {
+    let mut endorsement = load_active_endorsement(deps.storage, &endorsement_id)?;
+
+    // Only the endorser can edit an endorsement
+    if info.sender != endorsement.endorser {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut events = vec![];
+    if let Some(weight) = weight {
+        if weight == 0 || weight > 100 {
+            return Err(ContractError::InvalidEndorsementWeight { weight });
+        }
+
+        // Raising the weight spends the difference from the endorser's budget
+        if weight > endorsement.weight {
+            let config = CONFIG.load(deps.storage)?;
+            spend_endorsement_budget(
+                deps.storage,
+                &config,
+                &env,
+                &info.sender,
+                weight - endorsement.weight,
+            )?;
+        }
+
+        // Endorsee's score follows the weight change, keeping any collusion discount
+        let previous_weight = effective_weight(&endorsement, "update_endorsement")?;
+        endorsement.weight = weight;
+        if endorsement.status == EndorsementStatus::Active {
+            let mut endorsee_rep = REPUTATIONS.load(deps.storage, &endorsement.endorsee)?;
+            let previous_points =
+                to_score_points(previous_weight, "update_endorsement", &endorsement.endorsee)?;
+            endorsee_rep.score = sub_score(&endorsee_rep, previous_points, "update_endorsement")?;
+            let points = weight_points(&endorsement, "update_endorsement")?;
+            endorsee_rep.score = add_score(&endorsee_rep, points, "update_endorsement")?;
+            endorsee_rep.last_updated = env.block.time.seconds();
+            events = save_reputation(deps.storage, &env, &endorsee_rep)?;
+        }
+    }
+
+    if let Some(message) = message {
+        endorsement.message = message;
+    }
+
+    endorsement.updated_at = Some(env.block.time.seconds());
+    ENDORSEMENTS.save(deps.storage, endorsement_id.clone(), &endorsement)?;
+
+    Ok(Response::new()
+        .add_events(events)
+        .add_attribute("method", "update_endorsement")
+        .add_attribute("endorsement_id", endorsement_id)
+        .add_attribute("endorsee", endorsement.endorsee)
+        .add_attribute("weight", endorsement.weight.to_string()))
+}
+
+fn spend_endorsement_budget(
+    storage: &mut dyn Storage,
+    config: &Config,
+    env: &Env,
+    endorser: &Addr,
+    points: u32,
+) -> Result<(), ContractError> {
+    let score = REPUTATIONS
+        .may_load(storage, endorser)?
+        .map_or(SignedDecimal::zero(), |rep| rep.score);
+    let stored = ENDORSEMENT_BUDGETS.may_load(storage, endorser)?;
+    let mut budget = refreshed_endorsement_budget(config, score, stored, env.block.time.seconds());
+
+    if budget.points < points {
+        return Err(ContractError::InsufficientEndorsementBudget {
+            available: budget.points,
+            required: points,
+        });
+    }
+
+    budget.points -= points;
+    ENDORSEMENT_BUDGETS.save(storage, endorser, &budget)?;
+    Ok(())
+}
+
+// Budget capacity grows with reputation
+fn endorsement_budget_capacity(config: &Config, score: SignedDecimal) -> u32 {
+    // Negative scores earn no bonus
+    let score = Decimal::try_from(score).unwrap_or_default();
+    let bonus = score
+        .checked_mul(config.endorsement_budget_per_score)
+        .map_or(u32::MAX, |bonus| {
+            u32::try_from(bonus.to_uint_floor().u128()).unwrap_or(u32::MAX)
+        });
+    config.endorsement_budget_base.saturating_add(bonus)
+}
+
+// Refill the budget to capacity once per elapsed period
+fn refreshed_endorsement_budget(
+    config: &Config,
+    score: SignedDecimal,
+    stored: Option<EndorsementBudget>,
+    now: u64,
+) -> EndorsementBudget {
+    let capacity = endorsement_budget_capacity(config, score);
+    let period = config.endorsement_budget_period;
+
+    match stored {
+        Some(budget) if period > 0 && now < budget.last_refill.saturating_add(period) => budget,
+        Some(budget) if period > 0 => EndorsementBudget {
+            points: capacity,
+            last_refill: budget.last_refill + (now - budget.last_refill) / period * period,
+        },
+        _ => EndorsementBudget {
+            points: capacity,
+            last_refill: now,
+        },
+    }
+}
+
+fn load_active_endorsement(
+    storage: &dyn Storage,
+    endorsement_id: &str,
+) -> Result<Endorsement, ContractError> {
+    let endorsement = ENDORSEMENTS
+        .may_load(storage, endorsement_id.to_string())?
+        .ok_or_else(|| ContractError::EndorsementNotFound {
+            endorsement_id: endorsement_id.to_string(),
+        })?;
+
+    match endorsement.status {
+        EndorsementStatus::Active | EndorsementStatus::Pending => Ok(endorsement),
+        EndorsementStatus::Revoked => Err(ContractError::EndorsementRevoked {
+            endorsement_id: endorsement_id.to_string(),
+        }),
+        EndorsementStatus::Declined => Err(ContractError::EndorsementDeclined {
+            endorsement_id: endorsement_id.to_string(),
+        }),
+        EndorsementStatus::Withdrawn => Err(ContractError::EndorsementWithdrawn {
+            endorsement_id: endorsement_id.to_string(),
+        }),
+    }
+}
+
+pub fn execute_mint_skill_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    platform: String,
+    skill_level: u32,
+    token_uri: String,
+) -> Result<Response, ContractError> {
+    // Only the NFT authority sets levels by hand; proofs do it automatically through level rules
+    assert_nft_authority(deps.storage, &info.sender)?;
+    let config = CONFIG.load(deps.storage)?;
+
+    let recipient_addr = deps.api.addr_validate(&recipient)?;
+    // Only the admin lifts a revocation
+    if REVOKED_NFTS.has(deps.storage, (&recipient_addr, &platform)) && info.sender != config.admin {
+        return Err(ContractError::SkillNftRevoked { platform });
+    }
+
+    // Validate skill level
+    if !(1..=4).contains(&skill_level) {
+        return Err(ContractError::InvalidSkillLevel { level: skill_level });
+    }
+
+    // Validate platform
+    if !config.supported_platforms.contains(&platform) {
+        return Err(ContractError::UnsupportedPlatform { platform });
+    }
+
+    let proof_ids = supporting_proof_ids(
+        deps.storage,
+        &recipient_addr,
+        &platform,
+        skill_level,
+        env.block.time.seconds(),
+    )?;
+    let (token_id, change) = save_skill_nft(
+        deps.storage,
+        &env,
+        &recipient_addr,
+        &platform,
+        skill_level,
+        Some(token_uri),
+        proof_ids,
+        &info.sender,
+    )?;
+    BURNED_NFTS.remove(deps.storage, (&recipient_addr, &platform));
+    REVOKED_NFTS.remove(deps.storage, (&recipient_addr, &platform));
+    let events = evaluate_badges(deps.storage, &env, &recipient_addr)?;
+
+    Ok(Response::new()
+        .add_events(events)
+        .add_attribute("method", "mint_skill_nft")
+        .add_attribute("token_id", token_id)
+        .add_attribute(
+            "from_level",
+            change
+                .map_or(skill_level, |change| change.from_level)
+                .to_string(),
+        )
+        .add_attribute("owner", recipient_addr)
+        .add_attribute("platform", platform)
+        .add_attribute("skill_level", skill_level.to_string()))
+}
+
+pub fn execute_claim_skill_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    platform: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if !config.supported_platforms.contains(&platform) {
+        return Err(ContractError::UnsupportedPlatform { platform });
+    }
+    if REVOKED_NFTS.has(deps.storage, (&info.sender, &platform)) {
+        return Err(ContractError::SkillNftRevoked { platform });
+    }
+    let rules = LEVEL_RULES
+        .may_load(deps.storage, &platform)?
+        .unwrap_or_default();
+    if rules.is_empty() {
+        return Err(ContractError::ClaimRequirementNotMet {
+            requirement: format!("level rules for {}", platform),
+        });
+    }
+
+    // Best level among the sender's active verified proofs, and the proofs that earn it
+    let now = env.block.time.seconds();
+    if proof_levels(deps.storage, &info.sender, &platform, now)?.is_empty() {
+        return Err(ContractError::ClaimRequirementNotMet {
+            requirement: format!("an active verified {} proof", platform),
+        });
+    }
+    let (level, proof_ids) = earned_level(deps.storage, &info.sender, &platform, now)?;
+
+    let current_level = held_level(deps.storage, &info.sender, &platform, now)?;
+    if level <= current_level {
+        return Err(ContractError::ClaimRequirementNotMet {
+            requirement: level_requirement(&rules, current_level + 1),
+        });
+    }
+
+    let (token_id, _) = save_skill_nft(
+        deps.storage,
+        &env,
+        &info.sender,
+        &platform,
+        level,
+        None,
+        proof_ids,
+        &env.contract.address,
+    )?;
+    BURNED_NFTS.remove(deps.storage, (&info.sender, &platform));
+    let events = evaluate_badges(deps.storage, &env, &info.sender)?;
+
+    Ok(Response::new()
+        .add_events(events)
+        .add_attribute("method", "claim_skill_nft")
+        .add_attribute("token_id", token_id)
+        .add_attribute("from_level", current_level.to_string())
+        .add_attribute("owner", info.sender)
+        .add_attribute("platform", platform)
+        .add_attribute("skill_level", level.to_string()))
+}
+
+// Describe what a proof needs to reach `skill_level`, e.g. "Silver: totalSolutions >= 200"
+fn level_requirement(rules: &[LevelRule], skill_level: u32) -> String {
+    let options = rules
+        .iter()
+        .filter_map(|rule| {
+            let min = rule.thresholds.get(skill_level as usize - 1)?;
+            Some(format!("{} >= {}", rule.metric, min))
+        })
+        .collect::<Vec<_>>();
+    if options.is_empty() {
+        return format!("a level above {}", level_name(skill_level - 1));
+    }
+    format!("{}: {}", level_name(skill_level), options.join(" or "))
+}
+
+pub fn execute_burn_skill_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let nft = SKILL_NFTS
+        .may_load(deps.storage, token_id.clone())?
+        .ok_or(ContractError::NftNotFound { token_id })?;
+    if info.sender != nft.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    remove_skill_nft(deps.storage, &env, &nft, vec![])?;
+    BURNED_NFTS.save(
+        deps.storage,
+        (&nft.owner, &nft.platform),
+        &env.block.time.seconds(),
+    )?;
+    let mut events = vec![skill_nft_removed_event("skill_nft_burned", &nft, None)];
+    if !nft.platform.starts_with(BADGE_PLATFORM_PREFIX) {
+        events.extend(evaluate_badges(deps.storage, &env, &nft.owner)?);
+    }
+
+    Ok(Response::new()
+        .add_events(events)
+        .add_attribute("method", "burn_skill_nft")
+        .add_attribute("token_id", nft.token_id)
+        .add_attribute("owner", nft.owner))
+}
+
+pub fn execute_revoke_skill_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+    reason: String,
+) -> Result<Response, ContractError> {
+    assert_nft_authority(deps.storage, &info.sender)?;
+
+    let nft = SKILL_NFTS
+        .may_load(deps.storage, token_id.clone())?
+        .ok_or(ContractError::NftNotFound { token_id })?;
+
+    remove_skill_nft(deps.storage, &env, &nft, vec![])?;
+    REVOKED_NFTS.save(
+        deps.storage,
+        (&nft.owner, &nft.platform),
+        &env.block.time.seconds(),
+    )?;
+    let mut events = vec![skill_nft_removed_event(
+        "skill_nft_revoked",
+        &nft,
+        Some(&reason),
+    )];
+    if !nft.platform.starts_with(BADGE_PLATFORM_PREFIX) {
+        events.extend(evaluate_badges(deps.storage, &env, &nft.owner)?);
+    }
+
+    Ok(Response::new()
+        .add_events(events)
+        .add_attribute("method", "revoke_skill_nft")
+        .add_attribute("token_id", nft.token_id)
+        .add_attribute("owner", nft.owner)
+        .add_attribute("reason", reason))
+}
+
+// Accounts allowed to mint, level and revoke skill NFTs by hand
+fn assert_nft_authority(storage: &dyn Storage, sender: &Addr) -> Result<(), ContractError> {
+    let config = CONFIG.load(storage)?;
+    if *sender != config.admin && !config.minters.contains(sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    Ok(())
+}
+
+pub fn execute_revoke_proof(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proof_id: String,
+    reason: String,
+) -> Result<Response, ContractError> {
+    let mut proof =
+        PROOFS
+            .may_load(deps.storage, proof_id.clone())?
+            .ok_or(ContractError::ProofNotFound {
+                proof_id: proof_id.clone(),
+            })?;
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin && info.sender != proof.user {
+        return Err(ContractError::Unauthorized {});
+    }
+    if proof.revoked_at.is_some() {
+        return Err(ContractError::ProofRevoked { proof_id });
+    }
+
+    // A lapsed proof already left the reputation when ProcessExpiredProofs dropped its expiry
+    let counted = proof
+        .expires_at
+        .is_none_or(|expires_at| PROOF_EXPIRIES.has(deps.storage, (expires_at, &proof_id)));
+    proof.revoked_at = Some(env.block.time.seconds());
+    proof.revocation_reason = Some(reason.clone());
+    PROOFS.save(deps.storage, proof_id.clone(), &proof)?;
+    if let Some(expires_at) = proof.expires_at {
+        PROOF_EXPIRIES.remove(deps.storage, (expires_at, &proof_id));
+    }
+
+    let mut events = vec![];
+    if counted {
+        events.extend(retract_proof(deps.storage, &env, &proof, "revoke_proof")?);
+    }
+    events.extend(reconcile_platform_nft(
+        deps.storage,
+        &env,
+        &proof.user,
+        &proof.platform,
+        vec![proof_id.clone()],
+        "proof_revoked",
+    )?);
+    events.extend(evaluate_badges(deps.storage, &env, &proof.user)?);
+
+    Ok(Response::new()
+        .add_events(events)
+        .add_attribute("method", "revoke_proof")
+        .add_attribute("proof_id", proof_id)
+        .add_attribute("user", proof.user)
+        .add_attribute("platform", proof.platform)
+        .add_attribute("reason", reason))
+}
+
+pub fn execute_process_expired_proofs(
+    deps: DepsMut,
+    env: Env,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let now = env.block.time.seconds();
+    let limit = limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize;
+    let expired = PROOF_EXPIRIES
+        .keys(
+            deps.storage,
+            None,
+            Some(Bound::exclusive((now + 1, ""))),
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    // Group the lapsed proofs by the NFT they were backing
+    let mut affected: BTreeMap<(Addr, String), Vec<String>> = BTreeMap::new();
+    let mut events = vec![];
+    for (expires_at, proof_id) in &expired {
+        PROOF_EXPIRIES.remove(deps.storage, (*expires_at, proof_id));
+        let proof = PROOFS.load(deps.storage, proof_id.clone())?;
+        if proof.revoked_at.is_none() {
+            events.extend(retract_proof(deps.storage, &env, &proof, "expire_proof")?);
+            affected
+                .entry((proof.user, proof.platform))
+                .or_default()
+                .push(proof.id);
+        }
+    }
+
+    for ((owner, platform), proof_ids) in affected {
+        events.extend(reconcile_platform_nft(
+            deps.storage,
+            &env,
+            &owner,
+            &platform,
+            proof_ids,
+            "proof_expired",
+        )?);
+        events.extend(evaluate_badges(deps.storage, &env, &owner)?);
+    }
+
+    Ok(Response::new()
+        .add_events(events)
+        .add_attribute("method", "process_expired_proofs")
+        .add_attribute("processed", expired.len().to_string()))
+}
+
+// Take a proof that stopped counting out of its owner's reputation and the proof totals. The
+// platform's first-proof points and membership go too once no active proof is left on it.
+fn retract_proof(
+    storage: &mut dyn Storage,
+    env: &Env,
+    proof: &SkillProof,
+    operation: &str,
+) -> Result<Vec<Event>, ContractError> {
+    let now = env.block.time.seconds();
+    let platform_left = platform_proof_ids(storage, &proof.user, &proof.platform, now)?.is_empty();
+
+    let mut reputation = REPUTATIONS.load(storage, &proof.user)?;
+    reputation.total_proofs = reputation.total_proofs.saturating_sub(1);
+    reputation.score = sub_score(&reputation, score_points(PROOF_POINTS), operation)?;
+    if platform_left && reputation.platforms.contains(&proof.platform) {
+        reputation
+            .platforms
+            .retain(|platform| *platform != proof.platform);
+        reputation.score = sub_score(&reputation, score_points(NEW_PLATFORM_POINTS), operation)?;
+    }
+    reputation.last_updated = now;
+    let events = save_reputation(storage, env, &reputation)?;
+
+    if let Some(mut stats) = PLATFORM_STATS.may_load(storage, proof.platform.clone())? {
+        stats.total_proofs = stats.total_proofs.saturating_sub(1);
+        if platform_left && stats.top_users.contains(&proof.user) {
+            stats.top_users.retain(|user| *user != proof.user);
+            stats.total_users = stats.total_users.saturating_sub(1);
+        }
+        PLATFORM_STATS.save(storage, proof.platform.clone(), &stats)?;
+    }
+
+    let mut config = CONFIG.load(storage)?;
+    config.total_proofs = config.total_proofs.saturating_sub(1);
+    CONFIG.save(storage, &config)?;
+
+    Ok(events)
+}
+
+// Recompute the owner's NFT for a platform after `proof_ids` lapsed: move it down to the
+// level the remaining proofs earn, or revoke it once they earn none. A level set by hand
+// above what any proof earns only falls once the platform has no active proof left.
+fn reconcile_platform_nft(
+    storage: &mut dyn Storage,
+    env: &Env,
+    owner: &Addr,
+    platform: &str,
+    proof_ids: Vec<String>,
+    reason: &str,
+) -> StdResult<Option<Event>> {
+    let Some(token_id) = OWNER_PLATFORM_NFTS.may_load(storage, (owner, platform))? else {
+        return Ok(None);
+    };
+    let nft = SKILL_NFTS.load(storage, token_id)?;
+    let now = env.block.time.seconds();
+    let backing = proof_ids
+        .iter()
+        .any(|proof_id| nft.proof_ids.contains(proof_id));
+    if !backing && !platform_proof_ids(storage, owner, platform, now)?.is_empty() {
+        refresh_platform_nft(storage, env, owner, platform)?;
+        return Ok(None);
+    }
+
+    let (level, earning) = earned_level(storage, owner, platform, now)?;
+    if level == 0 {
+        remove_skill_nft(storage, env, &nft, proof_ids)?;
+        return Ok(Some(skill_nft_removed_event(
+            "skill_nft_revoked",
+            &nft,
+            Some(reason),
+        )));
+    }
+    if level >= nft.skill_level {
+        refresh_platform_nft(storage, env, owner, platform)?;
+        return Ok(None);
+    }
+
+    save_skill_nft(
+        storage,
+        env,
+        owner,
+        platform,
+        level,
+        None,
+        earning,
+        &env.contract.address,
+    )?;
+    Ok(Some(
+        Event::new("skill_nft_downgraded")
+            .add_attribute("token_id", &nft.token_id)
+            .add_attribute("owner", owner.as_str())
+            .add_attribute("platform", platform)
+            .add_attribute("from_level", nft.skill_level.to_string())
+            .add_attribute("to_level", level.to_string())
+            .add_attribute("reason", reason),
+    ))
+}
+
+// Delete an NFT and its index entries; the level history keeps a final drop to level 0
+fn remove_skill_nft(
+    storage: &mut dyn Storage,
+    env: &Env,
+    nft: &SkillNft,
+    proof_ids: Vec<String>,
+) -> StdResult<()> {
+    SKILL_NFTS.remove(storage, nft.token_id.clone());
+    OWNER_PLATFORM_NFTS.remove(storage, (&nft.owner, &nft.platform));
+
+    let mut user_nfts = USER_NFTS.may_load(storage, &nft.owner)?.unwrap_or_default();
+    user_nfts.retain(|token_id| *token_id != nft.token_id);
+    if user_nfts.is_empty() {
+        USER_NFTS.remove(storage, &nft.owner);
+    } else {
+        USER_NFTS.save(storage, &nft.owner, &user_nfts)?;
+    }
+    NUM_TOKENS.update(storage, |count| -> StdResult<_> {
+        Ok(count.saturating_sub(1))
+    })?;
+
+    let mut history = NFT_LEVEL_HISTORY
+        .may_load(storage, &nft.token_id)?
+        .unwrap_or_default();
+    history.push(NftLevelChange {
+        from_level: nft.skill_level,
+        to_level: 0,
+        changed_at: env.block.time.seconds(),
+        proof_ids,
+    });
+    NFT_LEVEL_HISTORY.save(storage, &nft.token_id, &history)
+}
+
+fn skill_nft_removed_event(kind: &str, nft: &SkillNft, reason: Option<&str>) -> Event {
+    let event = Event::new(kind)
+        .add_attribute("token_id", &nft.token_id)
+        .add_attribute("owner", &nft.owner)
+        .add_attribute("platform", &nft.platform)
+        .add_attribute("skill_level", nft.skill_level.to_string());
+    match reason {
+        Some(reason) => event.add_attribute("reason", reason),
+        None => event,
+    }
+}
+
+pub fn execute_update_reputation(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    user: String,
+    score_delta: SignedDecimal,
+    reason: String,
+) -> Result<Response, ContractError> {
+    // Only admin can manually update reputation
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let user_addr = deps.api.addr_validate(&user)?;
+    let mut reputation = REPUTATIONS
+        .may_load(deps.storage, &user_addr)?
+        .ok_or(ContractError::UserNotFound { user })?;
+
+    reputation.score = add_score(&reputation, score_delta, "update_reputation")?;
+    reputation.last_updated = env.block.time.seconds();
+
+    let events = save_reputation(deps.storage, &env, &reputation)?;
+
+    Ok(Response::new()
+        .add_events(events)
+        .add_attribute("method", "update_reputation")
+        .add_attribute("user", user_addr)
+        .add_attribute("score_delta", score_delta.to_string())
+        .add_attribute("reason", reason))
+}
+
+pub fn execute_run_merkle_job(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    // Only admin can publish reputation roots
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Resume the running export, or start the next epoch
+    let mut job = match MERKLE_JOB.may_load(deps.storage)? {
+        Some(job) if job.phase != MerkleJobPhase::Done => job,
+        previous => MerkleJob {
+            epoch: previous.map_or(1, |job| job.epoch + 1),
+            height: env.block.height,
+            phase: MerkleJobPhase::Leaves,
+            cursor: None,
+            level: 0,
+            level_size: 0,
+            position: 0,
+            leaf_count: 0,
+            started_at: env.block.time.seconds(),
+            completed_at: None,
+        },
+    };
+
+    // Each leaf or parent node hashed and each entry pruned consumes one unit of the budget
+    let mut budget = limit
+        .unwrap_or(DEFAULT_MERKLE_JOB_LIMIT)
+        .min(MAX_MERKLE_JOB_LIMIT) as usize;
+    let mut processed = 0;
+    while budget > 0 && job.phase != MerkleJobPhase::Done {
+        let used = advance_merkle_job(deps.storage, &env, &mut job, budget)?;
+        budget -= used;
+        processed += used;
+    }
+
+    MERKLE_JOB.save(deps.storage, &job)?;
+
+    let mut response = Response::new()
+        .add_attribute("method", "run_merkle_job")
+        .add_attribute("epoch", job.epoch.to_string())
+        .add_attribute("processed", processed.to_string())
+        .add_attribute("done", (job.phase == MerkleJobPhase::Done).to_string());
+    if job.phase == MerkleJobPhase::Done {
+        let root = MERKLE_ROOTS.load(deps.storage, job.epoch)?;
+        response = response.add_attribute("root", root.root);
+    }
+    Ok(response)
+}
+
+// Process one page of the current Merkle job phase. Returns the number of nodes hashed
+// or entries pruned.
+fn advance_merkle_job(
+    storage: &mut dyn Storage,
+    env: &Env,
+    job: &mut MerkleJob,
+    budget: usize,
+) -> StdResult<usize> {
+    match job.phase {
+        MerkleJobPhase::Leaves => {
+            let cursor = job.cursor.clone().map(Addr::unchecked);
+            // Accounts that moved away since the export height are exported under the
+            // address they had then
+            let users = REPUTATION_ACCOUNTS
+                .keys(
+                    storage,
+                    cursor.as_ref().map(Bound::exclusive),
+                    None,
+                    Order::Ascending,
+                )
+                .take(budget)
+                .collect::<StdResult<Vec<_>>>()?;
+
+            let tiers = REPUTATION_TIERS
+                .may_load_at_height(storage, job.height)?
+                .unwrap_or_default();
+            for user in &users {
+                // Accounts created after the export height are left out
+                let Some(reputation) = REPUTATIONS.may_load_at_height(storage, user, job.height)?
+                else {
+                    continue;
+                };
+                let leaf = MerkleLeaf {
+                    index: job.leaf_count,
+                    tier: tier_for_score(&tiers, reputation.score),
+                    score: reputation.score,
+                };
+                let hash = merkle_leaf_hash(user, leaf.score, leaf.tier.as_deref());
+                MERKLE_NODES.save(storage, (job.epoch, 0, leaf.index), &hex::encode(hash))?;
+                MERKLE_LEAVES.save(storage, (job.epoch, user), &leaf)?;
+                job.leaf_count += 1;
+            }
+
+            if let Some(user) = users.last() {
+                job.cursor = Some(user.to_string());
+            }
+            if users.len() < budget {
+                job.phase = MerkleJobPhase::Build;
+                job.cursor = None;
+                job.level_size = job.leaf_count;
+                if job.level_size <= 1 {
+                    finish_merkle_job(storage, env, job)?;
+                }
+            }
+            Ok(users.len())
+        }
+        MerkleJobPhase::Build => {
+            let parents = job.level_size.div_ceil(2);
+            let end = parents.min(job.position.saturating_add(budget as u32));
+            for index in job.position..end {
+                let left = MERKLE_NODES.load(storage, (job.epoch, job.level, 2 * index))?;
+                // An unpaired last node is carried up unchanged
+                let parent =
+                    match MERKLE_NODES.may_load(storage, (job.epoch, job.level, 2 * index + 1))? {
+                        Some(right) => hex::encode(merkle_pair_hash(
+                            &decode_merkle_hash(&left)?,
+                            &decode_merkle_hash(&right)?,
+                        )),
+                        None => left,
+                    };
+                MERKLE_NODES.save(storage, (job.epoch, job.level + 1, index), &parent)?;
+            }
+
+            let used = (end - job.position) as usize;
+            job.position = end;
+            if job.position == parents {
+                job.level += 1;
+                job.level_size = parents;
+                job.position = 0;
+                if parents == 1 {
+                    finish_merkle_job(storage, env, job)?;
+                }
+            }
+            Ok(used)
+        }
+        MerkleJobPhase::Prune => {
+            // Leaves go first, so any leaf still stored keeps its whole path for proofs
+            let previous = job.epoch - 1;
+            let users = MERKLE_LEAVES
+                .prefix(previous)
+                .keys(storage, None, None, Order::Ascending)
+                .take(budget)
+                .collect::<StdResult<Vec<_>>>()?;
+            for user in &users {
+                MERKLE_LEAVES.remove(storage, (previous, user));
+            }
+            let nodes = MERKLE_NODES
+                .sub_prefix(previous)
+                .keys(storage, None, None, Order::Ascending)
+                .take(budget - users.len())
+                .collect::<StdResult<Vec<_>>>()?;
+            for (level, index) in &nodes {
+                MERKLE_NODES.remove(storage, (previous, *level, *index));
+            }
+
+            let used = users.len() + nodes.len();
+            if used < budget {
+                job.phase = MerkleJobPhase::Done;
+                job.completed_at = Some(env.block.time.seconds());
+            }
+            Ok(used)
+        }
+        MerkleJobPhase::Done => Ok(0),
+    }
+}
+
+fn finish_merkle_job(storage: &mut dyn Storage, env: &Env, job: &mut MerkleJob) -> StdResult<()> {
+    // An empty export commits to the all-zero hash
+    let root = match job.level_size {
+        0 => hex::encode([0u8; 32]),
+        _ => MERKLE_NODES.load(storage, (job.epoch, job.level, 0))?,
+    };
+    MERKLE_ROOTS.save(
+        storage,
+        job.epoch,
+        &MerkleRoot {
+            epoch: job.epoch,
+            root,
+            height: job.height,
+            leaf_count: job.leaf_count,
+            created_at: env.block.time.seconds(),
+        },
+    )?;
+
+    // The previous epoch stays provable until the new root is published
+    if job.epoch > 1 {
+        job.phase = MerkleJobPhase::Prune;
+    } else {
+        job.phase = MerkleJobPhase::Done;
+        job.completed_at = Some(env.block.time.seconds());
+    }
+    Ok(())
+}
+
+// Leaves commit to "address|score|tier", with an empty tier for accounts below every tier
+fn merkle_leaf_hash(user: &Addr, score: SignedDecimal, tier: Option<&str>) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([MERKLE_LEAF_PREFIX]);
+    hasher.update(format!("{}|{}|{}", user, score, tier.unwrap_or_default()).as_bytes());
+    hasher.finalize().into()
+}
+
+// Pairs are hashed in sorted order so proofs need no left/right flags
+fn merkle_pair_hash(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (first, second) = if a <= b { (a, b) } else { (b, a) };
+    let mut hasher = Sha256::new();
+    hasher.update([MERKLE_NODE_PREFIX]);
+    hasher.update(first);
+    hasher.update(second);
+    hasher.finalize().into()
+}
+
+fn decode_merkle_hash(hash: &str) -> StdResult<[u8; 32]> {
+    let mut bytes = [0u8; 32];
+    hex::decode_to_slice(hash, &mut bytes)
+        .map_err(|_| StdError::generic_err(format!("Invalid hash: {}", hash)))?;
+    Ok(bytes)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn execute_issue_penalty(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    user: String,
+    score_penalty: Decimal,
+    restrictions: Vec<RestrictedAction>,
+    duration: Option<u64>,
+    reason: String,
+    dispute_id: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        if !config.dispute_resolvers.contains(&info.sender) {
+            return Err(ContractError::Unauthorized {});
+        }
+        if dispute_id.as_deref().is_none_or(|id| id.trim().is_empty()) {
+            return Err(ContractError::InvalidPenalty {
+                reason: "dispute resolvers must reference a dispute".to_string(),
+            });
+        }
+    }
+
+    if score_penalty.is_zero() && restrictions.is_empty() {
+        return Err(ContractError::InvalidPenalty {
+            reason: "penalty must deduct points or restrict actions".to_string(),
+        });
+    }
+    if duration == Some(0) {
+        return Err(ContractError::InvalidPenalty {
+            reason: "duration must be positive".to_string(),
+        });
+    }
+
+    let user_addr = deps.api.addr_validate(&user)?;
+    let mut reputation = REPUTATIONS
+        .may_load(deps.storage, &user_addr)?
+        .ok_or(ContractError::UserNotFound { user })?;
+
+    let now = env.block.time.seconds();
+    let mut events = vec![];
+    let mut floored = None;
+    if !score_penalty.is_zero() {
+        let points = to_score_points(score_penalty, "issue_penalty", &user_addr)?;
+        let score = sub_score(&reputation, points, "issue_penalty")?;
+        // The deduction stops at the floor; a score already below it is left where it is
+        reputation.score = match config.score_floor {
+            Some(floor) if score < floor.min(reputation.score) => {
+                floored = Some(floor.min(reputation.score));
+                floor.min(reputation.score)
+            }
+            _ => score,
+        };
+        reputation.last_updated = now;
+        events = save_reputation(deps.storage, &env, &reputation)?;
+    }
+
+    let penalty_id = PENALTY_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+    PENALTY_COUNT.save(deps.storage, &penalty_id)?;
+
+    let mut restrictions = restrictions;
+    restrictions.sort();
+    restrictions.dedup();
+
+    let penalty = Penalty {
+        id: penalty_id,
+        user: user_addr.clone(),
+        issued_by: info.sender,
+        dispute_id,
+        score_penalty,
+        restrictions,
+        reason: reason.clone(),
+        created_at: now,
+        expires_at: duration.map(|duration| now.saturating_add(duration)),
+        lifted_at: None,
+    };
+    PENALTIES.save(deps.storage, penalty_id, &penalty)?;
+
+    let mut penalty_ids = USER_PENALTIES
+        .may_load(deps.storage, &user_addr)?
+        .unwrap_or_default();
+    penalty_ids.push(penalty_id);
+    USER_PENALTIES.save(deps.storage, &user_addr, &penalty_ids)?;
+
+    Ok(Response::new()
+        .add_events(events)
+        .add_attribute("method", "issue_penalty")
+        .add_attribute("penalty_id", penalty_id.to_string())
+        .add_attribute("user", user_addr)
+        .add_attribute("score_penalty", score_penalty.to_string())
+        .add_attributes(floored.map(|score| ("floored_at", score.to_string())))
+        .add_attribute("reason", reason))
+}
+
+pub fn execute_lift_penalty(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    penalty_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin && !config.dispute_resolvers.contains(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut penalty = PENALTIES
+        .may_load(deps.storage, penalty_id)?
+        .ok_or(ContractError::PenaltyNotFound { penalty_id })?;
+    // Dispute resolvers only lift the penalties they issued
+    if info.sender != config.admin && penalty.issued_by != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if penalty.lifted_at.is_some() {
+        return Err(ContractError::PenaltyLifted { penalty_id });
     }
 
-    // Validate platform
-    if !config.supported_platforms.contains(&platform) {
-        return Err(ContractError::UnsupportedPlatform { platform });
+    penalty.lifted_at = Some(env.block.time.seconds());
+    PENALTIES.save(deps.storage, penalty_id, &penalty)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "lift_penalty")
+        .add_attribute("penalty_id", penalty_id.to_string())
+        .add_attribute("user", penalty.user))
+}
+
+pub fn execute_migrate_identity(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    old_address: String,
+    new_address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let is_guardian = config.guardians.contains(&info.sender);
+    if info.sender != config.admin && !is_guardian {
+        return Err(ContractError::Unauthorized {});
     }
 
-    // Generate token ID
-    let token_id = format!("{}:{}:{}", recipient_addr, platform, skill_level);
+    let old_addr = deps.api.addr_validate(&old_address)?;
+    let new_addr = deps.api.addr_validate(&new_address)?;
+    if old_addr == new_addr {
+        return Err(ContractError::InvalidIdentityMigration {
+            reason: "old and new address are the same".to_string(),
+        });
+    }
+    if !has_identity(deps.storage, &old_addr)? {
+        return Err(ContractError::InvalidIdentityMigration {
+            reason: format!("{} has no history to migrate", old_addr),
+        });
+    }
 
-    // Create NFT
-    let nft = SkillNft {
-        token_id: token_id.clone(),
-        owner: recipient_addr.clone(),
-        platform: platform.clone(),
-        skill_level,
-        token_uri,
-        created_at: env.block.time.seconds(),
-        last_updated: env.block.time.seconds(),
-        proof_count: 1, // Will be updated based on actual proofs
-    };
+    // Guardians vote on a proposal; the admin migrates directly
+    if info.sender != config.admin {
+        let mut migration = match IDENTITY_MIGRATIONS.may_load(deps.storage, &old_addr)? {
+            Some(migration) if migration.new_address != new_addr => {
+                return Err(ContractError::IdentityMigrationConflict {
+                    new_address: migration.new_address.to_string(),
+                });
+            }
+            Some(migration) => migration,
+            None => IdentityMigration {
+                old_address: old_addr.clone(),
+                new_address: new_addr.clone(),
+                approvals: vec![],
+                proposed_at: env.block.time.seconds(),
+            },
+        };
+        if !migration.approvals.contains(&info.sender) {
+            migration.approvals.push(info.sender.clone());
+        }
 
-    // Store NFT
-    SKILL_NFTS.save(deps.storage, token_id.clone(), &nft)?;
+        // Approvals from accounts no longer in the guardian set do not count
+        let approvals = migration
+            .approvals
+            .iter()
+            .filter(|guardian| config.guardians.contains(guardian))
+            .count();
+        if approvals < config.guardian_threshold as usize {
+            IDENTITY_MIGRATIONS.save(deps.storage, &old_addr, &migration)?;
+            return Ok(Response::new()
+                .add_attribute("method", "migrate_identity")
+                .add_attribute("old_address", old_addr)
+                .add_attribute("new_address", new_addr)
+                .add_attribute("approvals", approvals.to_string())
+                .add_attribute("migrated", "false"));
+        }
+    }
 
-    // Update user NFTs index
-    let mut user_nfts = USER_NFTS
-        .may_load(deps.storage, &recipient_addr)?
-        .unwrap_or_default();
-    user_nfts.push(token_id.clone());
-    USER_NFTS.save(deps.storage, &recipient_addr, &user_nfts)?;
+    IDENTITY_MIGRATIONS.remove(deps.storage, &old_addr);
+    let events = move_identity(deps.storage, &env, &old_addr, &new_addr)?;
 
     Ok(Response::new()
-        .add_attribute("method", "mint_skill_nft")
-        .add_attribute("token_id", token_id)
-        .add_attribute("owner", recipient_addr)
-        .add_attribute("platform", platform)
-        .add_attribute("skill_level", skill_level.to_string()))
+        .add_events(events)
+        .add_attribute("method", "migrate_identity")
+        .add_attribute("old_address", old_addr)
+        .add_attribute("new_address", new_addr)
+        .add_attribute("migrated", "true"))
 }
 
-pub fn execute_update_reputation(
+pub fn execute_cancel_identity_migration(
     deps: DepsMut,
-    env: Env,
     info: MessageInfo,
-    user: String,
-    score_delta: i32,
-    reason: String,
+    old_address: String,
 ) -> Result<Response, ContractError> {
-    // Only admin can manually update reputation
     let config = CONFIG.load(deps.storage)?;
-    if info.sender != config.admin {
+    if info.sender != config.admin && !config.guardians.contains(&info.sender) {
         return Err(ContractError::Unauthorized {});
     }
 
-    let user_addr = deps.api.addr_validate(&user)?;
-    let mut reputation = REPUTATIONS
-        .may_load(deps.storage, &user_addr)?
-        .ok_or(ContractError::UserNotFound { user })?;
+    let old_addr = deps.api.addr_validate(&old_address)?;
+    if !IDENTITY_MIGRATIONS.has(deps.storage, &old_addr) {
+        return Err(ContractError::IdentityMigrationNotFound { old_address });
+    }
+    IDENTITY_MIGRATIONS.remove(deps.storage, &old_addr);
 
-    reputation.score += score_delta;
-    reputation.last_updated = env.block.time.seconds();
+    Ok(Response::new()
+        .add_attribute("method", "cancel_identity_migration")
+        .add_attribute("old_address", old_addr))
+}
+
+// Whether the address has any on-chain history a migration would move
+fn has_identity(storage: &dyn Storage, addr: &Addr) -> StdResult<bool> {
+    Ok(REPUTATIONS.may_load(storage, addr)?.is_some()
+        || USER_PROOFS.has(storage, addr)
+        || USER_NFTS.has(storage, addr)
+        || USER_ENDORSEMENTS.has(storage, addr)
+        || USER_ENDORSEMENTS_GIVEN.has(storage, addr)
+        || USER_REVOKED_ENDORSEMENTS.has(storage, addr)
+        || PENDING_ENDORSEMENTS.has(storage, addr)
+        || USER_DECLINED_ENDORSEMENTS.has(storage, addr)
+        || USER_WITHDRAWN_ENDORSEMENTS.has(storage, addr)
+        || REQUESTS_SENT.has(storage, addr)
+        || REQUESTS_RECEIVED.has(storage, addr)
+        || USER_PENALTIES.has(storage, addr))
+}
 
-    REPUTATIONS.save(deps.storage, &user_addr, &reputation)?;
+// Re-home everything recorded for `old` under `new`. Records that only live in global
+// history (fulfilled requests, past Merkle epochs) keep the old address.
+fn move_identity(
+    storage: &mut dyn Storage,
+    env: &Env,
+    old: &Addr,
+    new: &Addr,
+) -> Result<Vec<Event>, ContractError> {
+    // The new address must be unused so nothing gets merged
+    if has_identity(storage, new)? {
+        return Err(ContractError::IdentityInUse {
+            address: new.to_string(),
+        });
+    }
 
-    Ok(Response::new()
-        .add_attribute("method", "update_reputation")
-        .add_attribute("user", user_addr)
-        .add_attribute("score_delta", score_delta.to_string())
-        .add_attribute("reason", reason))
+    // Proofs
+    for proof_id in move_entry(storage, &USER_PROOFS, old, new)?.unwrap_or_default() {
+        PROOFS.update(storage, proof_id, |proof| -> StdResult<_> {
+            let mut proof = proof.ok_or_else(|| StdError::not_found("SkillProof"))?;
+            proof.user = new.clone();
+            Ok(proof)
+        })?;
+    }
+
+    // NFTs
+    for token_id in move_entry(storage, &USER_NFTS, old, new)?.unwrap_or_default() {
+        let mut nft = SKILL_NFTS.load(storage, token_id.clone())?;
+        OWNER_PLATFORM_NFTS.remove(storage, (old, &nft.platform));
+        OWNER_PLATFORM_NFTS.save(storage, (new, &nft.platform), &token_id)?;
+        nft.owner = new.clone();
+        SKILL_NFTS.save(storage, token_id, &nft)?;
+    }
+    let burned = BURNED_NFTS
+        .prefix(old)
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (platform, burned_at) in burned {
+        BURNED_NFTS.remove(storage, (old, &platform));
+        BURNED_NFTS.save(storage, (new, &platform), &burned_at)?;
+    }
+    let revoked = REVOKED_NFTS
+        .prefix(old)
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (platform, revoked_at) in revoked {
+        REVOKED_NFTS.remove(storage, (old, &platform));
+        REVOKED_NFTS.save(storage, (new, &platform), &revoked_at)?;
+    }
+
+    // Achievements, ahead of the reputation so they are not unlocked again
+    let awards = USER_ACHIEVEMENTS
+        .prefix(old)
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (achievement_id, mut award) in awards {
+        USER_ACHIEVEMENTS.remove(storage, (old, &achievement_id));
+        award.user = new.clone();
+        USER_ACHIEVEMENTS.save(storage, (new, &achievement_id), &award)?;
+    }
+
+    // Reputation and leaderboards
+    let mut events = vec![];
+    if let Some(mut reputation) = REPUTATIONS.may_load(storage, old)? {
+        REPUTATIONS.remove(storage, old, env.block.height)?;
+        unindex_reputation(storage, &reputation)?;
+        count_reputation(storage, Some(&reputation), None)?;
+        reputation.user = new.clone();
+        events = save_reputation(storage, env, &reputation)?;
+    }
+    let stats = PLATFORM_STATS
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (platform, mut stats) in stats {
+        if let Some(user) = stats.top_users.iter_mut().find(|user| *user == old) {
+            *user = new.clone();
+            PLATFORM_STATS.save(storage, platform, &stats)?;
+        }
+    }
+    for trust_map in [
+        &TRUST_SCORES,
+        &TRUST_CURRENT,
+        &TRUST_NEXT,
+        &TRUST_OUT_WEIGHTS,
+    ] {
+        move_entry(storage, trust_map, old, new)?;
+    }
+
+    // Endorsements received
+    let mut received = move_entry(storage, &USER_ENDORSEMENTS, old, new)?.unwrap_or_default();
+    received.extend(move_entry(storage, &USER_REVOKED_ENDORSEMENTS, old, new)?.unwrap_or_default());
+    received.extend(move_entry(storage, &PENDING_ENDORSEMENTS, old, new)?.unwrap_or_default());
+    received
+        .extend(move_entry(storage, &USER_DECLINED_ENDORSEMENTS, old, new)?.unwrap_or_default());
+    received
+        .extend(move_entry(storage, &USER_WITHDRAWN_ENDORSEMENTS, old, new)?.unwrap_or_default());
+    for endorsement_id in received {
+        let mut endorsement = ENDORSEMENTS.load(storage, endorsement_id.clone())?;
+        let endorser = endorsement.endorser.clone();
+        let skill = endorsement.skill.clone();
+        if let Some(pair) = ENDORSEMENT_PAIRS.may_load(storage, (&endorser, old, &skill))? {
+            ENDORSEMENT_PAIRS.remove(storage, (&endorser, old, &skill));
+            ENDORSEMENT_PAIRS.save(storage, (&endorser, new, &skill), &pair)?;
+        }
+        if let Some(edge) = ACTIVE_ENDORSEMENT_EDGES.may_load(storage, (&endorser, old))? {
+            ACTIVE_ENDORSEMENT_EDGES.remove(storage, (&endorser, old));
+            ACTIVE_ENDORSEMENT_EDGES.save(storage, (&endorser, new), &edge)?;
+        }
+        endorsement.endorsee = new.clone();
+        ENDORSEMENTS.save(storage, endorsement_id, &endorsement)?;
+    }
+
+    // Endorsements given
+    for endorsement_id in
+        move_entry(storage, &USER_ENDORSEMENTS_GIVEN, old, new)?.unwrap_or_default()
+    {
+        let mut endorsement = ENDORSEMENTS.load(storage, endorsement_id.clone())?;
+        endorsement.endorser = new.clone();
+        ENDORSEMENTS.save(storage, endorsement_id, &endorsement)?;
+    }
+    let given_pairs = ENDORSEMENT_PAIRS
+        .sub_prefix(old)
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for ((endorsee, skill), pair) in given_pairs {
+        ENDORSEMENT_PAIRS.remove(storage, (old, &endorsee, &skill));
+        ENDORSEMENT_PAIRS.save(storage, (new, &endorsee, &skill), &pair)?;
+    }
+    let given_edges = ACTIVE_ENDORSEMENT_EDGES
+        .prefix(old)
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (endorsee, edge) in given_edges {
+        ACTIVE_ENDORSEMENT_EDGES.remove(storage, (old, &endorsee));
+        ACTIVE_ENDORSEMENT_EDGES.save(storage, (new, &endorsee), &edge)?;
+    }
+
+    // Open endorsement requests
+    for request_id in move_entry(storage, &REQUESTS_SENT, old, new)?.unwrap_or_default() {
+        let mut request = ENDORSEMENT_REQUESTS.load(storage, request_id.clone())?;
+        OPEN_ENDORSEMENT_REQUESTS.remove(storage, (&request.endorser, old, &request.skill));
+        OPEN_ENDORSEMENT_REQUESTS.save(
+            storage,
+            (&request.endorser, new, &request.skill),
+            &request_id,
+        )?;
+        request.requester = new.clone();
+        ENDORSEMENT_REQUESTS.save(storage, request_id, &request)?;
+    }
+    for request_id in move_entry(storage, &REQUESTS_RECEIVED, old, new)?.unwrap_or_default() {
+        let mut request = ENDORSEMENT_REQUESTS.load(storage, request_id.clone())?;
+        OPEN_ENDORSEMENT_REQUESTS.remove(storage, (old, &request.requester, &request.skill));
+        OPEN_ENDORSEMENT_REQUESTS.save(
+            storage,
+            (new, &request.requester, &request.skill),
+            &request_id,
+        )?;
+        request.endorser = new.clone();
+        ENDORSEMENT_REQUESTS.save(storage, request_id, &request)?;
+    }
+
+    // Moderation state follows the person, not the key
+    move_entry(storage, &ENDORSEMENT_BUDGETS, old, new)?;
+    if let Some(mut flag) = move_entry(storage, &COLLUSION_FLAGS, old, new)? {
+        flag.user = new.clone();
+        COLLUSION_FLAGS.save(storage, new, &flag)?;
+    }
+    // Flags outlive the counterparty's own flag once that is cleared, so check them all
+    let flags = COLLUSION_FLAGS
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (user, mut flag) in flags {
+        if let Some(counterparty) = flag.counterparties.iter_mut().find(|addr| *addr == old) {
+            *counterparty = new.clone();
+            COLLUSION_FLAGS.save(storage, &user, &flag)?;
+        }
+    }
+    for penalty_id in move_entry(storage, &USER_PENALTIES, old, new)?.unwrap_or_default() {
+        PENALTIES.update(storage, penalty_id, |penalty| -> StdResult<_> {
+            let mut penalty = penalty.ok_or_else(|| StdError::not_found("Penalty"))?;
+            penalty.user = new.clone();
+            Ok(penalty)
+        })?;
+    }
+
+    events.push(
+        Event::new("identity_migrated")
+            .add_attribute("old_address", old.as_str())
+            .add_attribute("new_address", new.as_str()),
+    );
+    Ok(events)
+}
+
+// Move a per-address record to another address, returning it
+fn move_entry<T>(
+    storage: &mut dyn Storage,
+    map: &Map<&Addr, T>,
+    old: &Addr,
+    new: &Addr,
+) -> StdResult<Option<T>>
+where
+    T: Serialize + DeserializeOwned,
+{
+    let value = map.may_load(storage, old)?;
+    if let Some(value) = &value {
+        map.remove(storage, old);
+        map.save(storage, new, value)?;
+    }
+    Ok(value)
 }
 
 pub fn execute_update_admin(
@@ -402,110 +3947,601 @@ pub fn execute_update_admin(
         .add_attribute("new_admin", new_admin_addr))
 }
 
-// Helper function to update leaderboards (simplified)
-fn update_leaderboards(
-    storage: &mut dyn cosmwasm_std::Storage,
-    platform: &str,
-    user: &Addr,
-) -> StdResult<()> {
-    // Update platform leaderboard
-    let mut platform_leaderboard = PLATFORM_LEADERBOARDS
-        .may_load(storage, platform.to_string())?
-        .unwrap_or_default();
+pub fn execute_run_trust_job(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    iterations: Option<u32>,
+    damping: Option<Decimal>,
+    limit: Option<u32>,
+    restart: bool,
+) -> Result<Response, ContractError> {
+    // Only admin can drive the trust computation
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Resume the running job unless a restart was requested, or start a new one
+    let mut job = match TRUST_JOB.may_load(deps.storage)? {
+        Some(job) if job.phase != TrustJobPhase::Done && !restart => job,
+        _ => {
+            let max_iterations = iterations.unwrap_or(DEFAULT_TRUST_ITERATIONS);
+            if max_iterations == 0 || max_iterations > MAX_TRUST_ITERATIONS {
+                return Err(ContractError::InvalidTrustParameters {
+                    reason: format!("iterations must be between 1 and {}", MAX_TRUST_ITERATIONS),
+                });
+            }
+
+            let damping = damping.unwrap_or(Decimal::percent(DEFAULT_TRUST_DAMPING_PERCENT));
+            if damping >= Decimal::one() {
+                return Err(ContractError::InvalidTrustParameters {
+                    reason: "damping must be below 1".to_string(),
+                });
+            }
+
+            TrustJob {
+                phase: TrustJobPhase::CountSeeds,
+                cursor: None,
+                iteration: 0,
+                max_iterations,
+                damping,
+                seed_count: 0,
+                started_at: env.block.time.seconds(),
+                completed_at: None,
+            }
+        }
+    };
+
+    // Each processed reputation or endorsement record consumes one unit of the budget
+    let mut budget = limit
+        .unwrap_or(DEFAULT_TRUST_JOB_LIMIT)
+        .min(MAX_TRUST_JOB_LIMIT) as usize;
+    let mut processed = 0;
+    while budget > 0 && job.phase != TrustJobPhase::Done {
+        let used = advance_trust_job(deps.storage, &env, &mut job, budget)?;
+        budget -= used;
+        processed += used;
+    }
+
+    TRUST_JOB.save(deps.storage, &job)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "run_trust_job")
+        .add_attribute("processed", processed.to_string())
+        .add_attribute("iteration", job.iteration.to_string())
+        .add_attribute("done", (job.phase == TrustJobPhase::Done).to_string()))
+}
+
+// Process one page of the current trust job phase, moving to the next phase once
+// the page comes back short. Returns the number of records processed.
+fn advance_trust_job(
+    storage: &mut dyn Storage,
+    env: &Env,
+    job: &mut TrustJob,
+    budget: usize,
+) -> Result<usize, ContractError> {
+    let phase = job.phase.clone();
+    let processed = match phase {
+        TrustJobPhase::CountSeeds | TrustJobPhase::Init | TrustJobPhase::Commit => {
+            let cursor = job.cursor.clone().map(Addr::unchecked);
+            let page = REPUTATIONS
+                .range(
+                    storage,
+                    cursor.as_ref().map(Bound::exclusive),
+                    None,
+                    Order::Ascending,
+                )
+                .take(budget)
+                .collect::<StdResult<Vec<_>>>()?;
+
+            for (user, reputation) in &page {
+                match phase {
+                    TrustJobPhase::CountSeeds => {
+                        TRUST_OUT_WEIGHTS.remove(storage, user);
+                        if reputation.total_proofs > 0 {
+                            job.seed_count += 1;
+                        }
+                    }
+                    TrustJobPhase::Init => {
+                        TRUST_NEXT.remove(storage, user);
+                        TRUST_CURRENT.save(storage, user, &pre_trust(job, reputation))?;
+                    }
+                    _ => {
+                        let propagated = TRUST_NEXT.may_load(storage, user)?.unwrap_or_default();
+                        TRUST_NEXT.remove(storage, user);
+                        let trust = propagated
+                            + (Decimal::one() - job.damping) * pre_trust(job, reputation);
+                        TRUST_CURRENT.save(storage, user, &trust)?;
+
+                        // Publish on the final iteration
+                        if job.iteration + 1 >= job.max_iterations {
+                            TRUST_SCORES.save(storage, user, &trust)?;
+                        }
+                    }
+                }
+            }
+
+            if let Some((user, _)) = page.last() {
+                job.cursor = Some(user.to_string());
+            }
+            page.len()
+        }
+        TrustJobPhase::OutWeights | TrustJobPhase::Propagate => {
+            let page = ENDORSEMENTS
+                .range(
+                    storage,
+                    job.cursor.clone().map(Bound::exclusive),
+                    None,
+                    Order::Ascending,
+                )
+                .take(budget)
+                .collect::<StdResult<Vec<_>>>()?;
+
+            for (_, endorsement) in &page {
+                let weight = effective_weight(endorsement, "run_trust_job")?;
+                if endorsement.status != EndorsementStatus::Active || weight.is_zero() {
+                    continue;
+                }
+
+                if phase == TrustJobPhase::OutWeights {
+                    TRUST_OUT_WEIGHTS.update(
+                        storage,
+                        &endorsement.endorser,
+                        |total| -> StdResult<_> { Ok(total.unwrap_or_default() + weight) },
+                    )?;
+                    continue;
+                }
+
+                let trust = TRUST_CURRENT
+                    .may_load(storage, &endorsement.endorser)?
+                    .unwrap_or_default();
+                if trust.is_zero() {
+                    continue;
+                }
+
+                // Endorsers split their trust across endorsees in proportion to weight.
+                // Endorsements added or raised after the OutWeights phase are skipped or
+                // capped so an endorser never hands out more than its own trust.
+                let out_weight = match TRUST_OUT_WEIGHTS.may_load(storage, &endorsement.endorser)? {
+                    Some(out_weight) if !out_weight.is_zero() => out_weight,
+                    _ => continue,
+                };
+                let share = job.damping * trust * (weight / out_weight).min(Decimal::one());
+                TRUST_NEXT.update(storage, &endorsement.endorsee, |total| -> StdResult<_> {
+                    Ok(total.unwrap_or_default() + share)
+                })?;
+            }
+
+            if let Some((id, _)) = page.last() {
+                job.cursor = Some(id.clone());
+            }
+            page.len()
+        }
+        TrustJobPhase::Done => 0,
+    };
 
-    if !platform_leaderboard.contains(user) {
-        platform_leaderboard.push(user.clone());
-        PLATFORM_LEADERBOARDS.save(storage, platform.to_string(), &platform_leaderboard)?;
+    if processed < budget {
+        job.cursor = None;
+        job.phase = match phase {
+            TrustJobPhase::CountSeeds => TrustJobPhase::Init,
+            TrustJobPhase::Init => TrustJobPhase::OutWeights,
+            TrustJobPhase::OutWeights => TrustJobPhase::Propagate,
+            TrustJobPhase::Propagate => TrustJobPhase::Commit,
+            TrustJobPhase::Commit => {
+                job.iteration += 1;
+                if job.iteration >= job.max_iterations {
+                    job.completed_at = Some(env.block.time.seconds());
+                    TrustJobPhase::Done
+                } else {
+                    TrustJobPhase::Propagate
+                }
+            }
+            TrustJobPhase::Done => TrustJobPhase::Done,
+        };
     }
 
-    // Update global leaderboard
-    let mut global_leaderboard = GLOBAL_LEADERBOARD.load(storage)?;
-    if !global_leaderboard.contains(user) {
-        global_leaderboard.push(user.clone());
-        GLOBAL_LEADERBOARD.save(storage, &global_leaderboard)?;
+    Ok(processed)
+}
+
+// Pre-trust is spread evenly over proof-verified accounts
+fn pre_trust(job: &TrustJob, reputation: &UserReputation) -> Decimal {
+    if reputation.total_proofs > 0 && job.seed_count > 0 {
+        Decimal::from_ratio(1u32, job.seed_count)
+    } else {
+        Decimal::zero()
     }
+}
 
-    Ok(())
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    update: ConfigUpdate,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for discount in [
+        update.reciprocal_endorsement_discount,
+        update.cycle_endorsement_discount,
+    ]
+    .into_iter()
+    .flatten()
+    {
+        if discount > Decimal::one() {
+            return Err(ContractError::InvalidDiscount {
+                discount: discount.to_string(),
+            });
+        }
+    }
+    if let Some(bonus) = update.qualified_endorsement_bonus {
+        if bonus > Decimal::one() {
+            return Err(ContractError::InvalidBonus {
+                bonus: bonus.to_string(),
+            });
+        }
+    }
+
+    if let Some(endorsement_cooldown) = update.endorsement_cooldown {
+        config.endorsement_cooldown = endorsement_cooldown;
+    }
+    if let Some(endorsement_budget_base) = update.endorsement_budget_base {
+        config.endorsement_budget_base = endorsement_budget_base;
+    }
+    if let Some(endorsement_budget_per_score) = update.endorsement_budget_per_score {
+        config.endorsement_budget_per_score = endorsement_budget_per_score;
+    }
+    if let Some(endorsement_budget_period) = update.endorsement_budget_period {
+        config.endorsement_budget_period = endorsement_budget_period;
+    }
+    if let Some(reciprocal_endorsement_discount) = update.reciprocal_endorsement_discount {
+        config.reciprocal_endorsement_discount = reciprocal_endorsement_discount;
+    }
+    if let Some(cycle_endorsement_discount) = update.cycle_endorsement_discount {
+        config.cycle_endorsement_discount = cycle_endorsement_discount;
+    }
+    if let Some(flag_collusion) = update.flag_collusion {
+        config.flag_collusion = flag_collusion;
+    }
+    if let Some(strict_skill_endorsements) = update.strict_skill_endorsements {
+        config.strict_skill_endorsements = strict_skill_endorsements;
+    }
+    if let Some(qualified_endorsement_bonus) = update.qualified_endorsement_bonus {
+        config.qualified_endorsement_bonus = qualified_endorsement_bonus;
+    }
+    if let Some(require_endorsement_acceptance) = update.require_endorsement_acceptance {
+        config.require_endorsement_acceptance = require_endorsement_acceptance;
+    }
+    if let Some(score_floor) = update.score_floor {
+        config.score_floor = Some(score_floor);
+    }
+    if update.clear_score_floor == Some(true) {
+        config.score_floor = None;
+    }
+    if let Some(dispute_resolvers) = update.dispute_resolvers {
+        config.dispute_resolvers = dispute_resolvers
+            .iter()
+            .map(|resolver| deps.api.addr_validate(resolver))
+            .collect::<StdResult<_>>()?;
+    }
+    if let Some(guardians) = update.guardians {
+        let mut guardians = guardians
+            .iter()
+            .map(|guardian| deps.api.addr_validate(guardian))
+            .collect::<StdResult<Vec<_>>>()?;
+        guardians.sort();
+        guardians.dedup();
+        config.guardians = guardians;
+    }
+    if let Some(guardian_threshold) = update.guardian_threshold {
+        config.guardian_threshold = guardian_threshold;
+    }
+    if let Some(auto_mint) = update.auto_mint {
+        config.auto_mint = auto_mint;
+    }
+    if let Some(onchain_metadata) = update.onchain_metadata {
+        config.onchain_metadata = onchain_metadata;
+    }
+    if let Some(proof_validity) = update.proof_validity {
+        config.proof_validity = Some(proof_validity);
+    }
+    if update.clear_proof_validity == Some(true) {
+        config.proof_validity = None;
+    }
+    if let Some(minters) = update.minters {
+        config.minters = minters
+            .iter()
+            .map(|minter| deps.api.addr_validate(minter))
+            .collect::<StdResult<_>>()?;
+    }
+    if config.guardian_threshold as usize > config.guardians.len() {
+        return Err(ContractError::InvalidGuardians {
+            reason: "threshold exceeds the number of guardians".to_string(),
+        });
+    }
+    if !config.guardians.is_empty() && config.guardian_threshold == 0 {
+        return Err(ContractError::InvalidGuardians {
+            reason: "threshold must be at least 1".to_string(),
+        });
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("method", "update_config"))
 }
 
 #[entry_point]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::GetUserProofs { user, platform } => {
-            to_json_binary(&query_user_proofs(deps, user, platform)?)
+            to_json_binary(&query_user_proofs(deps, env, user, platform)?)
         }
-        QueryMsg::GetProof { proof_id } => to_json_binary(&query_proof(deps, proof_id)?),
+        QueryMsg::GetProof { proof_id } => to_json_binary(&query_proof(deps, env, proof_id)?),
         QueryMsg::GetReputation { user } => to_json_binary(&query_reputation(deps, user)?),
         QueryMsg::GetEndorsements { user, skill } => {
             to_json_binary(&query_endorsements(deps, user, skill)?)
         }
-        QueryMsg::GetUserNfts { user } => to_json_binary(&query_user_nfts(deps, user)?),
-        QueryMsg::GetLeaderboard { platform, limit } => {
-            to_json_binary(&query_leaderboard(deps, platform, limit)?)
+        QueryMsg::GetEndorsementsGiven {
+            user,
+            skill,
+            start_after,
+            limit,
+        } => to_json_binary(&query_endorsements_given(
+            deps,
+            user,
+            skill,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::GetPendingEndorsements { user } => {
+            to_json_binary(&query_pending_endorsements(deps, user)?)
+        }
+        QueryMsg::GetEndorsementRequests { user, role } => {
+            to_json_binary(&query_endorsement_requests(deps, user, role)?)
+        }
+        QueryMsg::GetEndorsement { endorsement_id } => {
+            to_json_binary(&query_endorsement(deps, endorsement_id)?)
+        }
+        QueryMsg::GetLevelRules {} => to_json_binary(&query_level_rules(deps)?),
+        QueryMsg::GetReputationTiers {} => to_json_binary(&query_reputation_tiers(deps)?),
+        QueryMsg::GetSkillPlatforms {} => to_json_binary(&query_skill_platforms(deps)?),
+        QueryMsg::GetCollusionFlags { start_after, limit } => {
+            to_json_binary(&query_collusion_flags(deps, start_after, limit)?)
+        }
+        QueryMsg::GetEndorsementBudget { user } => {
+            to_json_binary(&query_endorsement_budget(deps, env, user)?)
+        }
+        QueryMsg::GetUserNfts { user } => to_json_binary(&query_user_nfts(deps, env, user)?),
+        QueryMsg::GetLeaderboard {
+            platform,
+            start_after,
+            limit,
+        } => to_json_binary(&query_leaderboard(deps, platform, start_after, limit)?),
+        QueryMsg::GetUserRank { user, neighbors } => {
+            to_json_binary(&query_user_rank(deps, user, neighbors)?)
         }
         QueryMsg::GetPlatformStats { platform } => {
             to_json_binary(&query_platform_stats(deps, platform)?)
         }
         QueryMsg::GetConfig {} => to_json_binary(&query_config(deps)?),
+        QueryMsg::GetTrustJob {} => to_json_binary(&query_trust_job(deps)?),
+        QueryMsg::GetReputationAtHeight { user, height } => {
+            to_json_binary(&query_reputation_at_height(deps, env, user, height)?)
+        }
+        QueryMsg::GetReputationsAtHeight {
+            height,
+            start_after,
+            limit,
+        } => to_json_binary(&query_reputations_at_height(
+            deps,
+            env,
+            height,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::GetNftSvg { token_id } => to_json_binary(&query_nft_svg(deps, env, token_id)?),
+        QueryMsg::GetSvgTemplate { platform, level } => {
+            to_json_binary(&query_svg_template(deps, platform, level)?)
+        }
+        QueryMsg::GetAchievements { start_after, limit } => {
+            to_json_binary(&query_achievements(deps, start_after, limit)?)
+        }
+        QueryMsg::GetUserAchievements { user } => {
+            to_json_binary(&query_user_achievements(deps, user)?)
+        }
+        QueryMsg::GetBadgeRule { id } => to_json_binary(&query_badge_rule(deps, id)?),
+        QueryMsg::GetBadgeRules { start_after, limit } => {
+            to_json_binary(&query_badge_rules(deps, start_after, limit)?)
+        }
+        QueryMsg::GetNftEvidence { token_id } => {
+            to_json_binary(&query_nft_evidence(deps, env, token_id)?)
+        }
+        QueryMsg::GetNftLevelHistory { token_id } => {
+            to_json_binary(&query_nft_level_history(deps, token_id)?)
+        }
+        QueryMsg::OwnerOf { token_id, .. } => to_json_binary(&query_owner_of(deps, token_id)?),
+        QueryMsg::NftInfo { token_id } => to_json_binary(&query_nft_info(deps, env, token_id)?),
+        QueryMsg::AllNftInfo { token_id, .. } => {
+            to_json_binary(&query_all_nft_info(deps, env, token_id)?)
+        }
+        QueryMsg::Tokens {
+            owner,
+            start_after,
+            limit,
+        } => to_json_binary(&query_tokens(deps, owner, start_after, limit)?),
+        QueryMsg::AllTokens { start_after, limit } => {
+            to_json_binary(&query_all_tokens(deps, start_after, limit)?)
+        }
+        QueryMsg::NumTokens {} => to_json_binary(&query_num_tokens(deps)?),
+        QueryMsg::ContractInfo {} => to_json_binary(&query_contract_info(deps)?),
+        QueryMsg::GetMerkleJob {} => to_json_binary(&query_merkle_job(deps)?),
+        QueryMsg::GetMerkleRoot { epoch } => to_json_binary(&query_merkle_root(deps, epoch)?),
+        QueryMsg::GetMerkleProof { epoch, user } => {
+            to_json_binary(&query_merkle_proof(deps, epoch, user)?)
+        }
+        QueryMsg::VerifyMerkleProof {
+            epoch,
+            user,
+            score,
+            tier,
+            proof,
+        } => to_json_binary(&query_verify_merkle_proof(
+            deps, epoch, user, score, tier, proof,
+        )?),
+        QueryMsg::GetIdentityMigration { old_address } => {
+            to_json_binary(&query_identity_migration(deps, old_address)?)
+        }
+        QueryMsg::GetPenalties { user, active_only } => {
+            to_json_binary(&query_penalties(deps, env, user, active_only)?)
+        }
     }
 }
 
 pub fn query_user_proofs(
     deps: Deps,
+    env: Env,
     user: String,
     platform: Option<String>,
 ) -> StdResult<Vec<ProofResponse>> {
     let user_addr = deps.api.addr_validate(&user)?;
     let proof_ids = USER_PROOFS.may_load(deps.storage, &user_addr)?.unwrap_or_default();
 
-    let mut proofs = vec![];
-    for proof_id in proof_ids {
-        if let Ok(proof) = PROOFS.load(deps.storage, proof_id) {
-            if platform.is_none() || platform.as_ref() == Some(&proof.platform) {
-                proofs.push(ProofResponse {
-                    id: proof.id,
-                    user: proof.user,
-                    platform: proof.platform,
-                    username: proof.username,
-                    skill_data: proof.skill_data,
-                    proof_hash: proof.proof_hash,
-                    timestamp: proof.timestamp,
-                    verified: proof.verified,
-                    metadata: proof.metadata,
-                });
-            }
+    let now = env.block.time.seconds();
+    let mut proofs = vec![];
+    for proof_id in proof_ids {
+        if let Ok(proof) = PROOFS.load(deps.storage, proof_id) {
+            if platform.is_none() || platform.as_ref() == Some(&proof.platform) {
+                proofs.push(proof_response(proof, now));
+            }
+        }
+    }
+
+    Ok(proofs)
+}
+
+pub fn query_proof(deps: Deps, env: Env, proof_id: String) -> StdResult<ProofResponse> {
+    let proof = PROOFS.load(deps.storage, proof_id)?;
+    Ok(proof_response(proof, env.block.time.seconds()))
+}
+
+fn proof_response(proof: SkillProof, now: u64) -> ProofResponse {
+    ProofResponse {
+        active: proof.is_active(now),
+        id: proof.id,
+        user: proof.user,
+        platform: proof.platform,
+        username: proof.username,
+        skill_data: proof.skill_data,
+        proof_hash: proof.proof_hash,
+        timestamp: proof.timestamp,
+        verified: proof.verified,
+        metadata: proof.metadata,
+        expires_at: proof.expires_at,
+        revoked_at: proof.revoked_at,
+        revocation_reason: proof.revocation_reason,
+    }
+}
+
+pub fn query_reputation(deps: Deps, user: String) -> StdResult<ReputationResponse> {
+    let user_addr = deps.api.addr_validate(&user)?;
+    let reputation = REPUTATIONS.load(deps.storage, &user_addr)?;
+    let tiers = REPUTATION_TIERS.may_load(deps.storage)?.unwrap_or_default();
+    Ok(ReputationResponse {
+        user: reputation.user,
+        score: reputation.score,
+        total_proofs: reputation.total_proofs,
+        endorsements_received: reputation.endorsements_received,
+        endorsements_given: reputation.endorsements_given,
+        last_updated: reputation.last_updated,
+        trust_score: TRUST_SCORES
+            .may_load(deps.storage, &user_addr)?
+            .unwrap_or_default(),
+        tier: tier_for_score(&tiers, reputation.score),
+    })
+}
+
+// Reputation as it stood at the start of block `height`
+pub fn query_reputation_at_height(
+    deps: Deps,
+    env: Env,
+    user: String,
+    height: u64,
+) -> StdResult<Option<ReputationSnapshotResponse>> {
+    assert_past_height(&env, height)?;
+    let user_addr = deps.api.addr_validate(&user)?;
+    let tiers = REPUTATION_TIERS
+        .may_load_at_height(deps.storage, height)?
+        .unwrap_or_default();
+    Ok(REPUTATIONS
+        .may_load_at_height(deps.storage, &user_addr, height)?
+        .map(|reputation| reputation_snapshot(&tiers, height, reputation)))
+}
+
+pub fn query_reputations_at_height(
+    deps: Deps,
+    env: Env,
+    height: u64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<ReputationSnapshotResponse>> {
+    assert_past_height(&env, height)?;
+    let limit = limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize;
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let tiers = REPUTATION_TIERS
+        .may_load_at_height(deps.storage, height)?
+        .unwrap_or_default();
+
+    // Accounts created after `height` have no value there and are skipped; accounts
+    // that moved away since are still listed under the address they had
+    let mut snapshots = vec![];
+    for user in REPUTATION_ACCOUNTS.keys(
+        deps.storage,
+        start_after.as_ref().map(Bound::exclusive),
+        None,
+        Order::Ascending,
+    ) {
+        if snapshots.len() == limit {
+            break;
+        }
+        let user = user?;
+        if let Some(reputation) = REPUTATIONS.may_load_at_height(deps.storage, &user, height)? {
+            snapshots.push(reputation_snapshot(&tiers, height, reputation));
         }
     }
 
-    Ok(proofs)
+    Ok(snapshots)
 }
 
-pub fn query_proof(deps: Deps, proof_id: String) -> StdResult<ProofResponse> {
-    let proof = PROOFS.load(deps.storage, proof_id)?;
-    Ok(ProofResponse {
-        id: proof.id,
-        user: proof.user,
-        platform: proof.platform,
-        username: proof.username,
-        skill_data: proof.skill_data,
-        proof_hash: proof.proof_hash,
-        timestamp: proof.timestamp,
-        verified: proof.verified,
-        metadata: proof.metadata,
-    })
+// Snapshots are only final once the height has been reached
+fn assert_past_height(env: &Env, height: u64) -> StdResult<()> {
+    if height > env.block.height {
+        return Err(StdError::generic_err(format!(
+            "Height {} is in the future (current height {})",
+            height, env.block.height
+        )));
+    }
+    Ok(())
 }
 
-pub fn query_reputation(deps: Deps, user: String) -> StdResult<ReputationResponse> {
-    let user_addr = deps.api.addr_validate(&user)?;
-    let reputation = REPUTATIONS.load(deps.storage, &user_addr)?;
-    Ok(ReputationResponse {
+// Tiers are resolved against the thresholds in force at `height`
+fn reputation_snapshot(
+    tiers: &[ReputationTier],
+    height: u64,
+    reputation: UserReputation,
+) -> ReputationSnapshotResponse {
+    ReputationSnapshotResponse {
+        tier: tier_for_score(tiers, reputation.score),
         user: reputation.user,
+        height,
         score: reputation.score,
         total_proofs: reputation.total_proofs,
         endorsements_received: reputation.endorsements_received,
         endorsements_given: reputation.endorsements_given,
-        last_updated: reputation.last_updated,
-    })
+    }
 }
 
 pub fn query_endorsements(
@@ -514,23 +4550,65 @@ pub fn query_endorsements(
     skill: Option<String>,
 ) -> StdResult<Vec<EndorsementResponse>> {
     let user_addr = deps.api.addr_validate(&user)?;
-    let endorsement_ids = USER_ENDORSEMENTS
+    let mut endorsement_ids = USER_ENDORSEMENTS
         .may_load(deps.storage, &user_addr)?
         .unwrap_or_default();
+    endorsement_ids.extend(
+        USER_REVOKED_ENDORSEMENTS
+            .may_load(deps.storage, &user_addr)?
+            .unwrap_or_default(),
+    );
 
     let mut endorsements = vec![];
     for endorsement_id in endorsement_ids {
         if let Ok(endorsement) = ENDORSEMENTS.load(deps.storage, endorsement_id) {
             if skill.is_none() || skill.as_ref() == Some(&endorsement.skill) {
-                endorsements.push(EndorsementResponse {
-                    id: endorsement.id,
-                    endorser: endorsement.endorser,
-                    endorsee: endorsement.endorsee,
-                    skill: endorsement.skill,
-                    message: endorsement.message,
-                    weight: endorsement.weight,
-                    timestamp: endorsement.timestamp,
-                });
+                endorsements.push(endorsement_response(endorsement)?);
+            }
+        }
+    }
+
+    Ok(endorsements)
+}
+
+pub fn query_endorsements_given(
+    deps: Deps,
+    user: String,
+    skill: Option<String>,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<EndorsementResponse>> {
+    let user_addr = deps.api.addr_validate(&user)?;
+    let limit = limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize;
+    let endorsement_ids = USER_ENDORSEMENTS_GIVEN
+        .may_load(deps.storage, &user_addr)?
+        .unwrap_or_default();
+
+    // Resume after the cursor; one outside this user's index would restart the listing
+    let start = match start_after {
+        Some(cursor) => {
+            let position = endorsement_ids
+                .iter()
+                .position(|id| id == &cursor)
+                .ok_or_else(|| {
+                    StdError::generic_err(format!(
+                        "Endorsement {} was not given by {}",
+                        cursor, user_addr
+                    ))
+                })?;
+            position + 1
+        }
+        None => 0,
+    };
+
+    let mut endorsements = vec![];
+    for endorsement_id in &endorsement_ids[start..] {
+        if endorsements.len() >= limit {
+            break;
+        }
+        if let Ok(endorsement) = ENDORSEMENTS.load(deps.storage, endorsement_id.clone()) {
+            if skill.is_none() || skill.as_ref() == Some(&endorsement.skill) {
+                endorsements.push(endorsement_response(endorsement)?);
             }
         }
     }
@@ -538,13 +4616,138 @@ pub fn query_endorsements(
     Ok(endorsements)
 }
 
-pub fn query_user_nfts(deps: Deps, user: String) -> StdResult<Vec<NftResponse>> {
+pub fn query_pending_endorsements(deps: Deps, user: String) -> StdResult<Vec<EndorsementResponse>> {
+    let user_addr = deps.api.addr_validate(&user)?;
+    let endorsement_ids = PENDING_ENDORSEMENTS
+        .may_load(deps.storage, &user_addr)?
+        .unwrap_or_default();
+
+    let mut endorsements = vec![];
+    for endorsement_id in endorsement_ids {
+        if let Ok(endorsement) = ENDORSEMENTS.load(deps.storage, endorsement_id) {
+            endorsements.push(endorsement_response(endorsement)?);
+        }
+    }
+
+    Ok(endorsements)
+}
+
+pub fn query_endorsement_requests(
+    deps: Deps,
+    user: String,
+    role: EndorsementRequestRole,
+) -> StdResult<Vec<EndorsementRequest>> {
+    let user_addr = deps.api.addr_validate(&user)?;
+    let request_ids = match role {
+        EndorsementRequestRole::Requester => REQUESTS_SENT.may_load(deps.storage, &user_addr)?,
+        EndorsementRequestRole::Endorser => REQUESTS_RECEIVED.may_load(deps.storage, &user_addr)?,
+    }
+    .unwrap_or_default();
+
+    let mut requests = vec![];
+    for request_id in request_ids {
+        if let Ok(request) = ENDORSEMENT_REQUESTS.load(deps.storage, request_id) {
+            requests.push(request);
+        }
+    }
+
+    Ok(requests)
+}
+
+pub fn query_endorsement(deps: Deps, endorsement_id: String) -> StdResult<EndorsementResponse> {
+    let endorsement = ENDORSEMENTS.load(deps.storage, endorsement_id)?;
+    endorsement_response(endorsement)
+}
+
+fn endorsement_response(endorsement: Endorsement) -> StdResult<EndorsementResponse> {
+    let effective_weight = effective_weight(&endorsement, "query_endorsement")
+        .map_err(|err| StdError::generic_err(err.to_string()))?;
+    Ok(EndorsementResponse {
+        id: endorsement.id,
+        endorser: endorsement.endorser,
+        endorsee: endorsement.endorsee,
+        skill: endorsement.skill,
+        message: endorsement.message,
+        weight: endorsement.weight,
+        effective_weight,
+        qualified: !endorsement.bonus.is_zero(),
+        timestamp: endorsement.timestamp,
+        status: endorsement.status,
+        updated_at: endorsement.updated_at,
+    })
+}
+
+pub fn query_level_rules(deps: Deps) -> StdResult<Vec<LevelRulesResponse>> {
+    LEVEL_RULES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(platform, rules)| LevelRulesResponse { platform, rules }))
+        .collect()
+}
+
+pub fn query_reputation_tiers(deps: Deps) -> StdResult<Vec<ReputationTier>> {
+    Ok(REPUTATION_TIERS.may_load(deps.storage)?.unwrap_or_default())
+}
+
+pub fn query_skill_platforms(deps: Deps) -> StdResult<Vec<SkillPlatformsResponse>> {
+    SKILL_PLATFORMS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(skill, platforms)| SkillPlatformsResponse { skill, platforms }))
+        .collect()
+}
+
+pub fn query_collusion_flags(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<CollusionFlag>> {
+    let limit = limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize;
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+
+    COLLUSION_FLAGS
+        .range(
+            deps.storage,
+            start_after.as_ref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| item.map(|(_, flag)| flag))
+        .collect()
+}
+
+pub fn query_endorsement_budget(
+    deps: Deps,
+    env: Env,
+    user: String,
+) -> StdResult<EndorsementBudgetResponse> {
+    let user_addr = deps.api.addr_validate(&user)?;
+    let config = CONFIG.load(deps.storage)?;
+    let score = REPUTATIONS
+        .may_load(deps.storage, &user_addr)?
+        .map_or(SignedDecimal::zero(), |rep| rep.score);
+    let stored = ENDORSEMENT_BUDGETS.may_load(deps.storage, &user_addr)?;
+    let budget = refreshed_endorsement_budget(&config, score, stored, env.block.time.seconds());
+
+    Ok(EndorsementBudgetResponse {
+        user: user_addr,
+        remaining: budget.points,
+        capacity: endorsement_budget_capacity(&config, score),
+        next_refill_at: budget
+            .last_refill
+            .saturating_add(config.endorsement_budget_period),
+    })
+}
+
+pub fn query_user_nfts(deps: Deps, env: Env, user: String) -> StdResult<Vec<NftResponse>> {
     let user_addr = deps.api.addr_validate(&user)?;
     let nft_ids = USER_NFTS.may_load(deps.storage, &user_addr)?.unwrap_or_default();
 
     let mut nfts = vec![];
     for nft_id in nft_ids {
         if let Ok(nft) = SKILL_NFTS.load(deps.storage, nft_id) {
+            let nft = live_skill_nft(deps.storage, nft, env.block.time.seconds())?;
             nfts.push(NftResponse {
                 token_id: nft.token_id,
                 owner: nft.owner,
@@ -553,6 +4756,7 @@ pub fn query_user_nfts(deps: Deps, user: String) -> StdResult<Vec<NftResponse>>
                 token_uri: nft.token_uri,
                 created_at: nft.created_at,
                 last_updated: nft.last_updated,
+                proof_count: nft.proof_count,
             });
         }
     }
@@ -560,47 +4764,511 @@ pub fn query_user_nfts(deps: Deps, user: String) -> StdResult<Vec<NftResponse>>
     Ok(nfts)
 }
 
+pub fn query_achievements(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Achievement>> {
+    let limit = limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize;
+    ACHIEVEMENTS
+        .range(
+            deps.storage,
+            start_after.as_deref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| item.map(|(_, achievement)| achievement))
+        .collect()
+}
+
+pub fn query_user_achievements(deps: Deps, user: String) -> StdResult<Vec<AchievementAward>> {
+    let user_addr = deps.api.addr_validate(&user)?;
+    USER_ACHIEVEMENTS
+        .prefix(&user_addr)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, award)| award))
+        .collect()
+}
+
+pub fn query_badge_rule(deps: Deps, id: String) -> StdResult<BadgeRule> {
+    BADGE_RULES.load(deps.storage, &id)
+}
+
+pub fn query_badge_rules(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<BadgeRule>> {
+    let limit = limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize;
+    BADGE_RULES
+        .range(
+            deps.storage,
+            start_after.as_deref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| item.map(|(_, rule)| rule))
+        .collect()
+}
+
+pub fn query_nft_evidence(deps: Deps, env: Env, token_id: String) -> StdResult<Vec<ProofResponse>> {
+    let now = env.block.time.seconds();
+    let nft = live_skill_nft(deps.storage, SKILL_NFTS.load(deps.storage, token_id)?, now)?;
+    nft.proof_ids
+        .into_iter()
+        .map(|proof_id| Ok(proof_response(PROOFS.load(deps.storage, proof_id)?, now)))
+        .collect()
+}
+
+// History outlives the token, so burned and revoked NFTs can still be traced
+pub fn query_nft_level_history(deps: Deps, token_id: String) -> StdResult<Vec<NftLevelChange>> {
+    match NFT_LEVEL_HISTORY.may_load(deps.storage, &token_id)? {
+        Some(history) => Ok(history),
+        None => {
+            SKILL_NFTS.load(deps.storage, token_id)?;
+            Ok(vec![])
+        }
+    }
+}
+
+pub fn query_owner_of(deps: Deps, token_id: String) -> StdResult<OwnerOfResponse> {
+    let nft = SKILL_NFTS.load(deps.storage, token_id)?;
+    Ok(OwnerOfResponse {
+        owner: nft.owner.to_string(),
+        approvals: vec![],
+    })
+}
+
+pub fn query_nft_info(
+    deps: Deps,
+    env: Env,
+    token_id: String,
+) -> StdResult<NftInfoResponse<SkillNftExtension>> {
+    let nft = SKILL_NFTS.load(deps.storage, token_id)?;
+    let nft = live_skill_nft(deps.storage, nft, env.block.time.seconds())?;
+    let image = match CONFIG.load(deps.storage)?.onchain_metadata {
+        true => Some(format!(
+            "data:image/svg+xml;base64,{}",
+            Binary::from(render_nft_svg(deps.storage, &nft)?.as_bytes()).to_base64()
+        )),
+        false => None,
+    };
+
+    let badge = badge_rule_for(deps.storage, &nft)?;
+    let level_name = nft_level_name(&nft);
+    let mut attributes = vec![
+        nft_trait(None, "Platform", nft.platform.clone()),
+        nft_trait(None, "Level", level_name.clone()),
+        nft_trait(Some("number"), "Skill Level", nft.skill_level.to_string()),
+        nft_trait(Some("number"), "Proof Count", nft.proof_count.to_string()),
+        nft_trait(Some("date"), "Issued", nft.created_at.to_string()),
+    ];
+    if let Some(attestor) = &nft.attestor {
+        attributes.push(nft_trait(None, "Attestor", attestor.to_string()));
+    }
+    let (name, description) = match badge {
+        Some(rule) => {
+            attributes.push(nft_trait(None, "Badge", rule.name.clone()));
+            (rule.name, rule.description)
+        }
+        None => (
+            format!("{} {} Badge", nft.platform, level_name),
+            format!(
+                "Soulbound {} skill badge at {} level, backed by {} verified proofs",
+                nft.platform, level_name, nft.proof_count
+            ),
+        ),
+    };
+
+    Ok(NftInfoResponse {
+        token_uri: Some(nft.token_uri).filter(|uri| !uri.is_empty()),
+        extension: SkillNftExtension {
+            name,
+            description,
+            image,
+            attributes,
+            platform: nft.platform,
+            skill_level: nft.skill_level,
+            level_name,
+            proof_count: nft.proof_count,
+            issued_at: nft.created_at,
+            last_updated: nft.last_updated,
+            attestor: nft.attestor,
+        },
+    })
+}
+
+pub fn query_nft_svg(deps: Deps, env: Env, token_id: String) -> StdResult<String> {
+    if !CONFIG.load(deps.storage)?.onchain_metadata {
+        return Err(StdError::generic_err("On-chain metadata is disabled"));
+    }
+    let nft = SKILL_NFTS.load(deps.storage, token_id)?;
+    let nft = live_skill_nft(deps.storage, nft, env.block.time.seconds())?;
+    render_nft_svg(deps.storage, &nft)
+}
+
+pub fn query_svg_template(
+    deps: Deps,
+    platform: String,
+    level: Option<u32>,
+) -> StdResult<Option<String>> {
+    SVG_TEMPLATES.may_load(deps.storage, (&platform, level.unwrap_or(0)))
+}
+
+fn nft_trait(display_type: Option<&str>, trait_type: &str, value: String) -> Trait {
+    Trait {
+        display_type: display_type.map(str::to_string),
+        trait_type: trait_type.to_string(),
+        value,
+    }
+}
+
+// Composite badges have a single level, so they are not named after a tier
+fn nft_level_name(nft: &SkillNft) -> String {
+    match nft.platform.starts_with(BADGE_PLATFORM_PREFIX) {
+        true => "Badge".to_string(),
+        false => level_name(nft.skill_level),
+    }
+}
+
+fn level_name(skill_level: u32) -> String {
+    match skill_level {
+        1..=4 => LEVEL_NAMES[skill_level as usize - 1].to_string(),
+        _ => format!("Level {}", skill_level),
+    }
+}
+
+// Fill the most specific template for the NFT's platform and level
+fn render_nft_svg(storage: &dyn Storage, nft: &SkillNft) -> StdResult<String> {
+    let template = match SVG_TEMPLATES.may_load(storage, (&nft.platform, nft.skill_level))? {
+        Some(template) => template,
+        None => SVG_TEMPLATES
+            .may_load(storage, (&nft.platform, 0))?
+            .unwrap_or_else(|| DEFAULT_SVG_TEMPLATE.to_string()),
+    };
+
+    let level_color = match nft.skill_level {
+        1..=4 => LEVEL_COLORS[nft.skill_level as usize - 1],
+        _ => "#6b7280",
+    };
+    let platform = match badge_rule_for(storage, nft)? {
+        Some(rule) => rule.name,
+        None => nft.platform.clone(),
+    };
+    let values = [
+        ("{{token_id}}", nft.token_id.clone()),
+        ("{{owner}}", nft.owner.to_string()),
+        ("{{platform}}", platform),
+        ("{{level}}", nft.skill_level.to_string()),
+        ("{{level_name}}", nft_level_name(nft)),
+        ("{{level_color}}", level_color.to_string()),
+        ("{{proof_count}}", nft.proof_count.to_string()),
+        ("{{issued_at}}", nft.created_at.to_string()),
+    ];
+    Ok(values.iter().fold(template, |svg, (placeholder, value)| {
+        svg.replace(placeholder, &escape_xml(value))
+    }))
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+pub fn query_all_nft_info(
+    deps: Deps,
+    env: Env,
+    token_id: String,
+) -> StdResult<AllNftInfoResponse<SkillNftExtension>> {
+    Ok(AllNftInfoResponse {
+        access: query_owner_of(deps, token_id.clone())?,
+        info: query_nft_info(deps, env, token_id)?,
+    })
+}
+
+pub fn query_tokens(
+    deps: Deps,
+    owner: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<TokensResponse> {
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let limit = limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize;
+
+    let mut tokens = USER_NFTS
+        .may_load(deps.storage, &owner_addr)?
+        .unwrap_or_default();
+    tokens.sort();
+    let tokens = tokens
+        .into_iter()
+        .filter(|token_id| start_after.as_ref().is_none_or(|start| token_id > start))
+        .take(limit)
+        .collect();
+
+    Ok(TokensResponse { tokens })
+}
+
+pub fn query_all_tokens(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<TokensResponse> {
+    let limit = limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize;
+    let tokens = SKILL_NFTS
+        .keys(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<_>>()?;
+
+    Ok(TokensResponse { tokens })
+}
+
+pub fn query_num_tokens(deps: Deps) -> StdResult<NumTokensResponse> {
+    Ok(NumTokensResponse {
+        count: NUM_TOKENS.may_load(deps.storage)?.unwrap_or_default(),
+    })
+}
+
+pub fn query_contract_info(deps: Deps) -> StdResult<ContractInfoResponse> {
+    let info = COLLECTION_INFO.load(deps.storage)?;
+    Ok(ContractInfoResponse {
+        name: info.name,
+        symbol: info.symbol,
+    })
+}
+
 pub fn query_leaderboard(
     deps: Deps,
     platform: Option<String>,
+    start_after: Option<LeaderboardCursor>,
     limit: Option<u32>,
 ) -> StdResult<Vec<LeaderboardEntry>> {
-    let limit = limit.unwrap_or(100).min(1000) as usize;
+    let limit = limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize;
+    let platform = platform.as_deref();
 
-    let user_addrs = if let Some(platform) = platform {
-        PLATFORM_LEADERBOARDS
-            .may_load(deps.storage, platform)?
-            .unwrap_or_default()
-    } else {
-        GLOBAL_LEADERBOARD.load(deps.storage)?
+    // Resume below the cursor's position
+    let cursor = match start_after {
+        Some(cursor) => Some((
+            score_key(cursor.score),
+            deps.api.addr_validate(&cursor.user)?,
+        )),
+        None => None,
     };
+    let max = cursor
+        .as_ref()
+        .map(|(key, user)| Bound::exclusive((*key, user)));
+
+    // Highest scores first
+    let keys = leaderboard_keys(deps.storage, platform, None, max, Order::Descending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
 
+    // Equal scores share a rank; the next score ranks below all of them
+    let tiers = REPUTATION_TIERS.may_load(deps.storage)?.unwrap_or_default();
+    let mut ranked: Option<(u128, u64)> = None;
     let mut entries = vec![];
-    for (rank, user_addr) in user_addrs.iter().enumerate() {
-        if rank >= limit {
-            break;
-        }
+    for (key, user_addr) in keys {
+        let rank = match ranked {
+            Some((previous, rank)) if previous == key => rank,
+            Some((previous, rank)) => rank + score_count(deps.storage, platform, previous)?,
+            None => accounts_above(deps.storage, platform, key)? + 1,
+        };
+        ranked = Some((key, rank));
+        entries.push(leaderboard_entry(
+            deps.storage,
+            &tiers,
+            user_addr,
+            rank as u32,
+        )?);
+    }
+    Ok(entries)
+}
 
-        if let Ok(reputation) = REPUTATIONS.load(deps.storage, user_addr) {
-            entries.push(LeaderboardEntry {
-                user: user_addr.clone(),
-                score: reputation.score,
-                rank: (rank + 1) as u32,
-                primary_platform: reputation.platforms.first().unwrap_or(&"Unknown".to_string()).clone(),
-                total_proofs: reputation.total_proofs,
-            });
+pub fn query_user_rank(
+    deps: Deps,
+    user: String,
+    neighbors: Option<u32>,
+) -> StdResult<UserRankResponse> {
+    let user_addr = deps.api.addr_validate(&user)?;
+    let reputation = REPUTATIONS.load(deps.storage, &user_addr)?;
+    if reputation.platforms.is_empty() {
+        return Err(StdError::generic_err(format!(
+            "{} is not on the leaderboard",
+            user_addr
+        )));
+    }
+    let neighbors = neighbors
+        .unwrap_or(DEFAULT_RANK_NEIGHBORS)
+        .min(MAX_RANK_NEIGHBORS) as usize;
+
+    let tiers = REPUTATION_TIERS.may_load(deps.storage)?.unwrap_or_default();
+    let global = leaderboard_rank(deps.storage, &tiers, None, &reputation, neighbors)?;
+    let platforms = reputation
+        .platforms
+        .iter()
+        .map(|platform| {
+            leaderboard_rank(deps.storage, &tiers, Some(platform), &reputation, neighbors)
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(UserRankResponse {
+        user: user_addr,
+        score: reputation.score,
+        global,
+        platforms,
+    })
+}
+
+// Rank of an account on one leaderboard: one more than the accounts with a higher
+// score, so accounts with equal scores share a rank
+fn leaderboard_rank(
+    storage: &dyn Storage,
+    tiers: &[ReputationTier],
+    platform: Option<&str>,
+    reputation: &UserReputation,
+    neighbors: usize,
+) -> StdResult<RankResponse> {
+    let key = score_key(reputation.score);
+    let rank = accounts_above(storage, platform, key)? + 1;
+    let total = match platform {
+        Some(platform) => PLATFORM_LEADERBOARD_SIZES
+            .may_load(storage, platform)?
+            .unwrap_or_default(),
+        None => GLOBAL_LEADERBOARD_SIZE
+            .may_load(storage)?
+            .unwrap_or_default(),
+    };
+    let below_count = total
+        .saturating_sub(rank - 1)
+        .saturating_sub(score_count(storage, platform, key)?);
+
+    // Walking away from the account, each new score moves the rank by its own count
+    // going up, or by the previous score's count going down
+    let mut above = vec![];
+    let mut ranked = (key, rank);
+    for item in leaderboard_keys(
+        storage,
+        platform,
+        Some(Bound::exclusive((key, &reputation.user))),
+        None,
+        Order::Ascending,
+    )
+    .take(neighbors)
+    {
+        let (next, user) = item?;
+        if next != ranked.0 {
+            ranked = (next, ranked.1 - score_count(storage, platform, next)?);
+        }
+        above.push(leaderboard_entry(storage, tiers, user, ranked.1 as u32)?);
+    }
+    let mut below = vec![];
+    let mut ranked = (key, rank);
+    for item in leaderboard_keys(
+        storage,
+        platform,
+        None,
+        Some(Bound::exclusive((key, &reputation.user))),
+        Order::Descending,
+    )
+    .take(neighbors)
+    {
+        let (next, user) = item?;
+        if next != ranked.0 {
+            ranked = (next, ranked.1 + score_count(storage, platform, ranked.0)?);
         }
+        below.push(leaderboard_entry(storage, tiers, user, ranked.1 as u32)?);
+    }
+
+    Ok(RankResponse {
+        platform: platform.map(str::to_string),
+        rank: rank as u32,
+        total,
+        percentile: Decimal::from_ratio(below_count * 100, total.max(1)),
+        above,
+        below,
+    })
+}
+
+// Accounts on the leaderboard with a higher score than `key`: at each level of the
+// prefix counts, the prefixes above the key's own under the same parent
+fn accounts_above(storage: &dyn Storage, platform: Option<&str>, key: u128) -> StdResult<u64> {
+    let mut above = 0;
+    for level in 1..=SCORE_PREFIX_LEVELS {
+        let prefix = score_prefix(key, level);
+        let min = Some(Bound::exclusive(prefix));
+        let max = Some(Bound::inclusive(prefix | 0xff));
+        let counts = match platform {
+            Some(platform) => PLATFORM_SCORE_COUNTS
+                .prefix((platform, level))
+                .range(storage, min, max, Order::Ascending)
+                .map(|item| item.map(|(_, count)| count))
+                .collect::<StdResult<Vec<_>>>()?,
+            None => GLOBAL_SCORE_COUNTS
+                .prefix(level)
+                .range(storage, min, max, Order::Ascending)
+                .map(|item| item.map(|(_, count)| count))
+                .collect::<StdResult<Vec<_>>>()?,
+        };
+        above += counts.into_iter().sum::<u64>();
     }
+    Ok(above)
+}
 
-    // Sort by score (in a real implementation, this would be more efficient)
-    entries.sort_by(|a, b| b.score.cmp(&a.score));
+// Accounts on the leaderboard with exactly the score at `key`
+fn score_count(storage: &dyn Storage, platform: Option<&str>, key: u128) -> StdResult<u64> {
+    let level = SCORE_PREFIX_LEVELS;
+    Ok(match platform {
+        Some(platform) => PLATFORM_SCORE_COUNTS.may_load(storage, (platform, level, key))?,
+        None => GLOBAL_SCORE_COUNTS.may_load(storage, (level, key))?,
+    }
+    .unwrap_or_default())
+}
 
-    // Update ranks after sorting
-    for (i, entry) in entries.iter_mut().enumerate() {
-        entry.rank = (i + 1) as u32;
+// (score_key, user) keys of the global leaderboard, or of a platform's
+fn leaderboard_keys<'a>(
+    storage: &'a dyn Storage,
+    platform: Option<&'a str>,
+    min: Option<Bound<'a, (u128, &'a Addr)>>,
+    max: Option<Bound<'a, (u128, &'a Addr)>>,
+    order: Order,
+) -> Box<dyn Iterator<Item = StdResult<(u128, Addr)>> + 'a> {
+    match platform {
+        Some(platform) => PLATFORM_SCORE_INDEX
+            .sub_prefix(platform)
+            .keys(storage, min, max, order),
+        None => GLOBAL_SCORE_INDEX.keys(storage, min, max, order),
     }
+}
 
-    Ok(entries)
+fn leaderboard_entry(
+    storage: &dyn Storage,
+    tiers: &[ReputationTier],
+    user: Addr,
+    rank: u32,
+) -> StdResult<LeaderboardEntry> {
+    let reputation = REPUTATIONS.load(storage, &user)?;
+    Ok(LeaderboardEntry {
+        user,
+        score: reputation.score,
+        rank,
+        primary_platform: reputation
+            .platforms
+            .first()
+            .unwrap_or(&"Unknown".to_string())
+            .clone(),
+        total_proofs: reputation.total_proofs,
+        tier: tier_for_score(tiers, reputation.score),
+    })
 }
 
 pub fn query_platform_stats(deps: Deps, platform: String) -> StdResult<PlatformStats> {
@@ -614,5 +5282,123 @@ pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
         total_proofs: config.total_proofs,
         total_users: config.total_users,
         supported_platforms: config.supported_platforms,
+        endorsement_cooldown: config.endorsement_cooldown,
+        endorsement_budget_base: config.endorsement_budget_base,
+        endorsement_budget_per_score: config.endorsement_budget_per_score,
+        endorsement_budget_period: config.endorsement_budget_period,
+        reciprocal_endorsement_discount: config.reciprocal_endorsement_discount,
+        cycle_endorsement_discount: config.cycle_endorsement_discount,
+        flag_collusion: config.flag_collusion,
+        strict_skill_endorsements: config.strict_skill_endorsements,
+        qualified_endorsement_bonus: config.qualified_endorsement_bonus,
+        require_endorsement_acceptance: config.require_endorsement_acceptance,
+        score_floor: config.score_floor,
+        dispute_resolvers: config.dispute_resolvers,
+        guardians: config.guardians,
+        guardian_threshold: config.guardian_threshold,
+        auto_mint: config.auto_mint,
+        onchain_metadata: config.onchain_metadata,
+        proof_validity: config.proof_validity,
+        minters: config.minters,
+    })
+}
+
+pub fn query_identity_migration(
+    deps: Deps,
+    old_address: String,
+) -> StdResult<Option<IdentityMigration>> {
+    let old_addr = deps.api.addr_validate(&old_address)?;
+    IDENTITY_MIGRATIONS.may_load(deps.storage, &old_addr)
+}
+
+pub fn query_merkle_job(deps: Deps) -> StdResult<Option<MerkleJob>> {
+    MERKLE_JOB.may_load(deps.storage)
+}
+
+pub fn query_merkle_root(deps: Deps, epoch: Option<u64>) -> StdResult<Option<MerkleRoot>> {
+    match epoch {
+        Some(epoch) => MERKLE_ROOTS.may_load(deps.storage, epoch),
+        None => MERKLE_ROOTS
+            .range(deps.storage, None, None, Order::Descending)
+            .next()
+            .transpose()
+            .map(|latest| latest.map(|(_, root)| root)),
+    }
+}
+
+pub fn query_merkle_proof(deps: Deps, epoch: u64, user: String) -> StdResult<MerkleProofResponse> {
+    let user_addr = deps.api.addr_validate(&user)?;
+    let root = MERKLE_ROOTS.load(deps.storage, epoch)?;
+    let leaf = MERKLE_LEAVES.load(deps.storage, (epoch, &user_addr))?;
+
+    // Collect the sibling of the node on the path at every level below the root
+    let mut proof = vec![];
+    let mut index = leaf.index;
+    let mut level = 0;
+    let mut level_size = root.leaf_count;
+    while level_size > 1 {
+        if let Some(sibling) = MERKLE_NODES.may_load(deps.storage, (epoch, level, index ^ 1))? {
+            proof.push(sibling);
+        }
+        index /= 2;
+        level += 1;
+        level_size = level_size.div_ceil(2);
+    }
+
+    Ok(MerkleProofResponse {
+        epoch,
+        root: root.root,
+        leaf: MERKLE_NODES.load(deps.storage, (epoch, 0, leaf.index))?,
+        user: user_addr,
+        score: leaf.score,
+        tier: leaf.tier,
+        proof,
     })
 }
+
+pub fn query_verify_merkle_proof(
+    deps: Deps,
+    epoch: u64,
+    user: String,
+    score: SignedDecimal,
+    tier: Option<String>,
+    proof: Vec<String>,
+) -> StdResult<bool> {
+    let user_addr = deps.api.addr_validate(&user)?;
+    let root = MERKLE_ROOTS.load(deps.storage, epoch)?;
+
+    let mut hash = merkle_leaf_hash(&user_addr, score, tier.as_deref());
+    for sibling in &proof {
+        hash = merkle_pair_hash(&hash, &decode_merkle_hash(sibling)?);
+    }
+
+    Ok(hex::encode(hash) == root.root)
+}
+
+pub fn query_penalties(
+    deps: Deps,
+    env: Env,
+    user: String,
+    active_only: Option<bool>,
+) -> StdResult<Vec<PenaltyResponse>> {
+    let user_addr = deps.api.addr_validate(&user)?;
+    let now = env.block.time.seconds();
+    let penalty_ids = USER_PENALTIES
+        .may_load(deps.storage, &user_addr)?
+        .unwrap_or_default();
+
+    let mut penalties = vec![];
+    for penalty_id in penalty_ids.into_iter().rev() {
+        let penalty = PENALTIES.load(deps.storage, penalty_id)?;
+        let active = penalty.is_active(now);
+        if active || !active_only.unwrap_or(false) {
+            penalties.push(PenaltyResponse { penalty, active });
+        }
+    }
+
+    Ok(penalties)
+}
+
+pub fn query_trust_job(deps: Deps) -> StdResult<Option<TrustJob>> {
+    TRUST_JOB.may_load(deps.storage)
+}
@@ -47,4 +47,121 @@ pub enum ContractError {
 
     #[error("Invalid skill data format")]
     InvalidSkillData {},
+
+    #[error("Duplicate endorsement: endorser already endorsed this user for this skill")]
+    DuplicateEndorsement {},
+
+    #[error("Endorsement cooldown active until {available_at}")]
+    EndorsementCooldown { available_at: u64 },
+
+    #[error("Insufficient endorsement budget: {available} points available, {required} required")]
+    InsufficientEndorsementBudget { available: u32, required: u32 },
+
+    #[error("Endorser has no verified proofs or NFTs on platforms related to {skill}")]
+    UnqualifiedEndorser { skill: String },
+
+    #[error("An endorsement request for this skill is already open")]
+    DuplicateEndorsementRequest {},
+
+    #[error("Too many open endorsement requests: at most {limit} at a time")]
+    TooManyEndorsementRequests { limit: u32 },
+
+    #[error("Endorser has too many open endorsement requests: at most {limit} at a time")]
+    EndorserRequestsFull { limit: u32 },
+
+    #[error("Endorsement request not found: {request_id}")]
+    EndorsementRequestNotFound { request_id: String },
+
+    #[error("Endorsement request is not open: {request_id}")]
+    EndorsementRequestClosed { request_id: String },
+
+    #[error("Endorsement is not pending: {endorsement_id}")]
+    EndorsementNotPending { endorsement_id: String },
+
+    #[error("Endorsement was declined: {endorsement_id}")]
+    EndorsementDeclined { endorsement_id: String },
+
+    #[error("Endorsement was withdrawn: {endorsement_id}")]
+    EndorsementWithdrawn { endorsement_id: String },
+
+    #[error("Endorsement not found: {endorsement_id}")]
+    EndorsementNotFound { endorsement_id: String },
+
+    #[error("Endorsement already revoked: {endorsement_id}")]
+    EndorsementRevoked { endorsement_id: String },
+
+    #[error("Invalid discount: {discount}. Must not exceed 1")]
+    InvalidDiscount { discount: String },
+
+    #[error("Invalid bonus: {bonus}. Must not exceed 1")]
+    InvalidBonus { bonus: String },
+
+    #[error("Account may not {action} while penalty {penalty_id} is active")]
+    AccountRestricted { action: String, penalty_id: u64 },
+
+    #[error("Penalty not found: {penalty_id}")]
+    PenaltyNotFound { penalty_id: u64 },
+
+    #[error("Penalty already lifted: {penalty_id}")]
+    PenaltyLifted { penalty_id: u64 },
+
+    #[error("Invalid penalty: {reason}")]
+    InvalidPenalty { reason: String },
+
+    #[error("Invalid level rules: {reason}")]
+    InvalidLevelRules { reason: String },
+
+    #[error("Invalid SVG template: {reason}")]
+    InvalidSvgTemplate { reason: String },
+
+    #[error("Skill NFTs are soulbound and cannot be transferred or approved")]
+    NonTransferable {},
+
+    #[error("Invalid guardians: {reason}")]
+    InvalidGuardians { reason: String },
+
+    #[error("Invalid identity migration: {reason}")]
+    InvalidIdentityMigration { reason: String },
+
+    #[error("Address already has on-chain history: {address}")]
+    IdentityInUse { address: String },
+
+    #[error("A migration of this identity to {new_address} is already pending")]
+    IdentityMigrationConflict { new_address: String },
+
+    #[error("No identity migration pending for {old_address}")]
+    IdentityMigrationNotFound { old_address: String },
+
+    #[error("Proof already revoked: {proof_id}")]
+    ProofRevoked { proof_id: String },
+
+    #[error("Skill NFT for {platform} was revoked and only the admin can mint it again")]
+    SkillNftRevoked { platform: String },
+
+    #[error("Claim requirement not met: {requirement}")]
+    ClaimRequirementNotMet { requirement: String },
+
+    #[error("Invalid badge rule: {reason}")]
+    InvalidBadgeRule { reason: String },
+
+    #[error("Badge rule not found: {id}")]
+    BadgeRuleNotFound { id: String },
+
+    #[error("Invalid achievement: {reason}")]
+    InvalidAchievement { reason: String },
+
+    #[error("Achievement not found: {id}")]
+    AchievementNotFound { id: String },
+
+    #[error("Reputation score overflow in {operation} for {account}")]
+    ScoreOverflow { operation: String, account: String },
+
+    #[error("Invalid migration: {reason}")]
+    InvalidMigration { reason: String },
+
+    #[error("Invalid reputation tiers: {reason}")]
+    InvalidTiers { reason: String },
+
+    #[error("Invalid trust job parameters: {reason}")]
+    InvalidTrustParameters { reason: String },
 }
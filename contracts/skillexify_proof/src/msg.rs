@@ -1,12 +1,24 @@
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Binary, Decimal, SignedDecimal};
+use cw_utils::Expiration;
+
+use crate::state::{
+    Achievement, BadgeRule, EndorsementStatus, LevelRule, Penalty, ReputationTier, RestrictedAction,
+};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
     pub admin: Option<String>,
+    #[serde(default)]
+    pub name: Option<String>, // cw721 collection name
+    #[serde(default)]
+    pub symbol: Option<String>, // cw721 collection symbol
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
@@ -21,7 +33,7 @@ pub enum ExecuteMsg {
     // Update user's reputation score
     UpdateReputation {
         user: String,
-        score_delta: i32,
+        score_delta: SignedDecimal,
         reason: String,
     },
     // Add peer endorsement
@@ -31,17 +43,221 @@ pub enum ExecuteMsg {
         message: String,
         weight: u32, // endorsement weight based on endorser's reputation
     },
-    // Mint/Update skill NFT
+    // Ask a specific address to endorse the sender for a skill. A requester keeps a limited
+    // number of requests open at a time.
+    RequestEndorsement {
+        endorser: String,
+        skill: String,
+        message: Option<String>,
+    },
+    // Close an open endorsement request without endorsing (endorser only)
+    DismissEndorsementRequest {
+        request_id: String,
+    },
+    // Accept a pending endorsement so it counts toward reputation (endorsee only)
+    AcceptEndorsement {
+        endorsement_id: String,
+    },
+    // Reject a pending endorsement (endorsee only)
+    DeclineEndorsement {
+        endorsement_id: String,
+    },
+    // Withdraw an endorsement (endorser only). A pending one is marked withdrawn rather than
+    // revoked, since it never counted.
+    RevokeEndorsement {
+        endorsement_id: String,
+    },
+    // Correct the message or weight of an endorsement (endorser only)
+    UpdateEndorsement {
+        endorsement_id: String,
+        message: Option<String>,
+        weight: Option<u32>,
+    },
+    // Mint the recipient's NFT for a platform, or set its level in place (admin or minter)
     MintSkillNft {
         recipient: String,
         platform: String,
         skill_level: u32, // 1-4 (Bronze, Silver, Gold, Platinum)
         token_uri: String,
     },
+    // Mint or upgrade the sender's NFT for a platform to the level their active verified
+    // proofs earn under the platform's level rules
+    ClaimSkillNft {
+        platform: String,
+    },
+    // Burn one of the sender's skill NFTs. Proofs and badge rules do not award a burned
+    // NFT again; ClaimSkillNft brings a platform NFT back.
+    BurnSkillNft {
+        token_id: String,
+    },
+    // Remove a skill NFT from its owner (admin or minter). Proofs, claims and badge rules do
+    // not award it again until the admin mints it with MintSkillNft.
+    RevokeSkillNft {
+        token_id: String,
+        reason: String,
+    },
+    // Withdraw a proof (admin or the proof's owner). Its reputation points and proof counts
+    // are taken back, along with the platform's first-proof points once no active proof is
+    // left there. The platform's NFT drops to the level the remaining proofs earn, and is
+    // revoked once they earn none.
+    RevokeProof {
+        proof_id: String,
+        reason: String,
+    },
+    // Take the points of proofs whose validity has ended off their owners' reputations and
+    // downgrade or revoke the NFTs they backed, checking up to `limit` expired proofs (anyone)
+    ProcessExpiredProofs {
+        limit: Option<u32>,
+    },
     // Update admin
     UpdateAdmin {
         new_admin: String,
     },
+    // Update contract parameters (admin only)
+    UpdateConfig(ConfigUpdate),
+    // Replace the reputation tier thresholds (admin only)
+    SetReputationTiers {
+        tiers: Vec<ReputationTier>,
+    },
+    // Set the platforms relevant to a skill; an empty list removes the mapping (admin only)
+    SetSkillPlatforms {
+        skill: String,
+        platforms: Vec<String>,
+    },
+    // Set the rules that turn a platform's proofs into NFT levels; an empty list disables
+    // automatic minting for the platform (admin only)
+    SetLevelRules {
+        platform: String,
+        rules: Vec<LevelRule>,
+    },
+    // Upload the SVG badge template for a platform level, or for all of its levels when
+    // `level` is omitted; no template removes it (admin only). Templates may use the
+    // placeholders {{token_id}}, {{owner}}, {{platform}}, {{level}}, {{level_name}},
+    // {{level_color}}, {{proof_count}} and {{issued_at}}.
+    SetSvgTemplate {
+        platform: String,
+        level: Option<u32>,
+        template: Option<String>,
+    },
+    // Create or replace a composite badge rule (admin only). Saving a rule evaluates no
+    // account: badges are awarded or revoked under it the next time their owner is
+    // evaluated, on a proof or NFT change or through RefreshBadges.
+    SetBadgeRule {
+        rule: BadgeRule,
+    },
+    // Stop awarding a composite badge; badges already awarded are kept (admin only)
+    RemoveBadgeRule {
+        id: String,
+    },
+    // Award or revoke a user's composite badges against the current rules (anyone)
+    RefreshBadges {
+        user: String,
+    },
+    // Create or replace an achievement (admin only). Accounts already past the threshold
+    // unlock it on their next reputation change.
+    SetAchievement {
+        achievement: Achievement,
+    },
+    // Stop awarding an achievement; records already unlocked are kept (admin only)
+    RemoveAchievement {
+        id: String,
+    },
+    // Clear a user's collusion flag after review (admin only)
+    ClearCollusionFlag {
+        user: String,
+    },
+    // Advance the trust propagation job by up to `limit` items (admin only).
+    // `iterations` and `damping` only apply when a new job is started.
+    // `restart` abandons a job in progress and starts over.
+    RunTrustJob {
+        iterations: Option<u32>,
+        damping: Option<Decimal>,
+        limit: Option<u32>,
+        #[serde(default)]
+        restart: bool,
+    },
+    // Advance the Merkle export of all reputations by up to `limit` nodes (admin only).
+    // A new export starts at the current height once the previous one is done. The
+    // previous epoch's leaves and nodes are deleted after the new root is published.
+    RunMerkleJob {
+        limit: Option<u32>,
+    },
+    // Penalize an account (admin or dispute resolver). Resolvers must cite the dispute.
+    // Restrictions last `duration` seconds, or until lifted when omitted.
+    IssuePenalty {
+        user: String,
+        score_penalty: Decimal,
+        restrictions: Vec<RestrictedAction>,
+        duration: Option<u64>,
+        reason: String,
+        dispute_id: Option<String>,
+    },
+    // End a penalty's restrictions early (admin, or the resolver who issued it); deducted
+    // points are not restored
+    LiftPenalty {
+        penalty_id: u64,
+    },
+    // Move all proofs, reputation, endorsements and NFTs to a new address. Runs at once for
+    // the admin; guardians each submit the same migration until the threshold is reached.
+    MigrateIdentity {
+        old_address: String,
+        new_address: String,
+    },
+    // Drop a pending guardian migration (admin or guardian)
+    CancelIdentityMigration {
+        old_address: String,
+    },
+    // cw721 transfer and approval messages; skill NFTs are soulbound so these always fail
+    TransferNft {
+        recipient: String,
+        token_id: String,
+    },
+    SendNft {
+        contract: String,
+        token_id: String,
+        msg: Binary,
+    },
+    Approve {
+        spender: String,
+        token_id: String,
+        expires: Option<Expiration>,
+    },
+    Revoke {
+        spender: String,
+        token_id: String,
+    },
+    ApproveAll {
+        operator: String,
+        expires: Option<Expiration>,
+    },
+    RevokeAll {
+        operator: String,
+    },
+}
+
+// Admin-tunable parameters; omitted fields are left unchanged
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct ConfigUpdate {
+    pub endorsement_cooldown: Option<u64>,
+    pub endorsement_budget_base: Option<u32>,
+    pub endorsement_budget_per_score: Option<Decimal>,
+    pub endorsement_budget_period: Option<u64>,
+    pub reciprocal_endorsement_discount: Option<Decimal>,
+    pub cycle_endorsement_discount: Option<Decimal>,
+    pub flag_collusion: Option<bool>,
+    pub strict_skill_endorsements: Option<bool>,
+    pub qualified_endorsement_bonus: Option<Decimal>,
+    pub require_endorsement_acceptance: Option<bool>,
+    pub score_floor: Option<SignedDecimal>,
+    pub clear_score_floor: Option<bool>,
+    pub dispute_resolvers: Option<Vec<String>>,
+    pub guardians: Option<Vec<String>>,
+    pub guardian_threshold: Option<u32>,
+    pub auto_mint: Option<bool>,
+    pub onchain_metadata: Option<bool>,
+    pub proof_validity: Option<u64>,
+    pub clear_proof_validity: Option<bool>,
+    pub minters: Option<Vec<String>>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -60,11 +276,57 @@ pub enum QueryMsg {
     GetReputation {
         user: String,
     },
-    // Get user's endorsements
+    // Get user's reputation as of the start of a past block; null if the account did not exist
+    GetReputationAtHeight {
+        user: String,
+        height: u64,
+    },
+    // Export every account's reputation as of the start of a past block, ordered by address
+    GetReputationsAtHeight {
+        height: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    // Get user's endorsements (revoked ones are included and marked as such)
     GetEndorsements {
         user: String,
         skill: Option<String>,
     },
+    // Get endorsements given by a user, oldest first
+    GetEndorsementsGiven {
+        user: String,
+        skill: Option<String>,
+        start_after: Option<String>, // an endorsement_id given by `user`
+        limit: Option<u32>,
+    },
+    // Get endorsements awaiting a user's acceptance
+    GetPendingEndorsements {
+        user: String,
+    },
+    // Get open endorsement requests the user sent (as requester) or received (as endorser)
+    GetEndorsementRequests {
+        user: String,
+        role: EndorsementRequestRole,
+    },
+    // Get specific endorsement by ID
+    GetEndorsement {
+        endorsement_id: String,
+    },
+    // Get the NFT level rules of every platform
+    GetLevelRules {},
+    // Get reputation tier thresholds
+    GetReputationTiers {},
+    // Get skill -> platform relevance mappings
+    GetSkillPlatforms {},
+    // Get accounts flagged for endorsement rings (moderation)
+    GetCollusionFlags {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    // Get user's remaining endorsement budget
+    GetEndorsementBudget {
+        user: String,
+    },
     // Get user's NFTs
     GetUserNfts {
         user: String,
@@ -72,14 +334,123 @@ pub enum QueryMsg {
     // Get leaderboard
     GetLeaderboard {
         platform: Option<String>,
+        start_after: Option<LeaderboardCursor>, // last entry of the previous page
         limit: Option<u32>,
     },
+    // Get a user's global and per-platform rank, with up to `neighbors` accounts on
+    // either side of them
+    GetUserRank {
+        user: String,
+        neighbors: Option<u32>,
+    },
     // Get platform statistics
     GetPlatformStats {
         platform: String,
     },
     // Get contract config
     GetConfig {},
+    // Render an NFT's SVG badge (requires on-chain metadata mode)
+    GetNftSvg {
+        token_id: String,
+    },
+    // Get the uploaded SVG template for a platform level (level omitted: platform default)
+    GetSvgTemplate {
+        platform: String,
+        level: Option<u32>,
+    },
+    // Get the level changes of an NFT, oldest first
+    GetNftLevelHistory {
+        token_id: String,
+    },
+    // List achievement definitions by id
+    GetAchievements {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    // Get the achievements a user has unlocked
+    GetUserAchievements {
+        user: String,
+    },
+    // Get a composite badge rule
+    GetBadgeRule {
+        id: String,
+    },
+    // List composite badge rules by id
+    GetBadgeRules {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    // Get the proofs backing an NFT
+    GetNftEvidence {
+        token_id: String,
+    },
+    // cw721: owner of a token. Skill NFTs carry no approvals.
+    OwnerOf {
+        token_id: String,
+        include_expired: Option<bool>,
+    },
+    // cw721: token URI and skill metadata
+    NftInfo {
+        token_id: String,
+    },
+    // cw721: OwnerOf and NftInfo combined
+    AllNftInfo {
+        token_id: String,
+        include_expired: Option<bool>,
+    },
+    // cw721: token ids held by an owner, ordered by id
+    Tokens {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    // cw721: all token ids, ordered by id
+    AllTokens {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    // cw721: number of tokens in the collection
+    NumTokens {},
+    // cw721: collection name and symbol
+    ContractInfo {},
+    // Get trust propagation job progress
+    GetTrustJob {},
+    // Get the Merkle export job progress
+    GetMerkleJob {},
+    // Get the root of an export epoch, or the latest when omitted
+    GetMerkleRoot {
+        epoch: Option<u64>,
+    },
+    // Get a user's leaf and inclusion path in an export epoch. Only the latest published
+    // epoch keeps its leaves, until the next export publishes its root.
+    GetMerkleProof {
+        epoch: u64,
+        user: String,
+    },
+    // Check a leaf and path against the root of an export epoch
+    VerifyMerkleProof {
+        epoch: u64,
+        user: String,
+        score: SignedDecimal,
+        tier: Option<String>,
+        proof: Vec<String>,
+    },
+    // Get the guardian migration pending for an address, if any
+    GetIdentityMigration {
+        old_address: String,
+    },
+    // Get penalties issued to a user, newest first
+    GetPenalties {
+        user: String,
+        active_only: Option<bool>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EndorsementRequestRole {
+    Requester,
+    Endorser,
 }
 
 // Responses
@@ -94,16 +465,33 @@ pub struct ProofResponse {
     pub timestamp: u64,
     pub verified: bool,
     pub metadata: Option<String>,
+    pub expires_at: Option<u64>,
+    pub revoked_at: Option<u64>,
+    pub revocation_reason: Option<String>,
+    pub active: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct ReputationResponse {
     pub user: Addr,
-    pub score: i32,
+    pub score: SignedDecimal,
     pub total_proofs: u32,
     pub endorsements_received: u32,
     pub endorsements_given: u32,
     pub last_updated: u64,
+    pub trust_score: Decimal,
+    pub tier: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ReputationSnapshotResponse {
+    pub user: Addr,
+    pub height: u64,
+    pub score: SignedDecimal,
+    pub total_proofs: u32,
+    pub endorsements_received: u32,
+    pub endorsements_given: u32,
+    pub tier: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -114,7 +502,11 @@ pub struct EndorsementResponse {
     pub skill: String,
     pub message: String,
     pub weight: u32,
+    pub effective_weight: Decimal,
+    pub qualified: bool,
     pub timestamp: u64,
+    pub status: EndorsementStatus,
+    pub updated_at: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -126,15 +518,66 @@ pub struct NftResponse {
     pub token_uri: String,
     pub created_at: u64,
     pub last_updated: u64,
+    pub proof_count: u32,
 }
 
+// cw721 extension carried by NftInfo, following the common on-chain metadata layout
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct LeaderboardEntry {
+pub struct SkillNftExtension {
+    pub name: String,
+    pub description: String,
+    pub image: Option<String>, // SVG data URI in on-chain metadata mode
+    pub attributes: Vec<Trait>,
+    pub platform: String,
+    pub skill_level: u32,
+    pub level_name: String,
+    pub proof_count: u32,
+    pub issued_at: u64,
+    pub last_updated: u64,
+    pub attestor: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Trait {
+    pub display_type: Option<String>,
+    pub trait_type: String,
+    pub value: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UserRankResponse {
     pub user: Addr,
-    pub score: i32,
+    pub score: SignedDecimal,
+    pub global: RankResponse,
+    pub platforms: Vec<RankResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RankResponse {
+    pub platform: Option<String>, // None for the global leaderboard
     pub rank: u32,
+    pub total: u64,
+    pub percentile: Decimal, // share of ranked accounts below the user, in percent
+    pub above: Vec<LeaderboardEntry>, // closest ranks first
+    pub below: Vec<LeaderboardEntry>, // closest ranks first
+}
+
+// Copied from the last `LeaderboardEntry` of a page; the page after it starts below
+// that position, and its ranks are counted by the contract
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LeaderboardCursor {
+    pub user: String,
+    pub score: SignedDecimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LeaderboardEntry {
+    pub user: Addr,
+    pub score: SignedDecimal,
+    pub rank: u32, // accounts with equal scores share a rank
     pub primary_platform: String,
     pub total_proofs: u32,
+    pub tier: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -143,4 +586,59 @@ pub struct ConfigResponse {
     pub total_proofs: u32,
     pub total_users: u32,
     pub supported_platforms: Vec<String>,
+    pub endorsement_cooldown: u64,
+    pub endorsement_budget_base: u32,
+    pub endorsement_budget_per_score: Decimal,
+    pub endorsement_budget_period: u64,
+    pub reciprocal_endorsement_discount: Decimal,
+    pub cycle_endorsement_discount: Decimal,
+    pub flag_collusion: bool,
+    pub strict_skill_endorsements: bool,
+    pub qualified_endorsement_bonus: Decimal,
+    pub require_endorsement_acceptance: bool,
+    pub score_floor: Option<SignedDecimal>,
+    pub dispute_resolvers: Vec<Addr>,
+    pub guardians: Vec<Addr>,
+    pub guardian_threshold: u32,
+    pub auto_mint: bool,
+    pub onchain_metadata: bool,
+    pub proof_validity: Option<u64>,
+    pub minters: Vec<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MerkleProofResponse {
+    pub epoch: u64,
+    pub root: String,
+    pub user: Addr,
+    pub score: SignedDecimal,
+    pub tier: Option<String>,
+    pub leaf: String,
+    pub proof: Vec<String>, // sibling hashes from the leaf up to the root
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PenaltyResponse {
+    pub penalty: Penalty,
+    pub active: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LevelRulesResponse {
+    pub platform: String,
+    pub rules: Vec<LevelRule>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SkillPlatformsResponse {
+    pub skill: String,
+    pub platforms: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EndorsementBudgetResponse {
+    pub user: Addr,
+    pub remaining: u32,
+    pub capacity: u32,
+    pub next_refill_at: u64,
 }
@@ -1,5 +1,5 @@
-use cosmwasm_std::Addr;
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Decimal, Empty, OverflowError, SignedDecimal};
+use cw_storage_plus::{Item, Map, SnapshotItem, SnapshotMap, Strategy};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -9,6 +9,74 @@ pub struct Config {
     pub total_proofs: u32,
     pub total_users: u32,
     pub supported_platforms: Vec<String>,
+    #[serde(default)]
+    pub endorsement_cooldown: u64, // seconds before the same endorsement can be given again
+    #[serde(default = "default_endorsement_budget_base")]
+    pub endorsement_budget_base: u32, // budget points every endorser starts with
+    #[serde(default = "default_endorsement_budget_per_score")]
+    pub endorsement_budget_per_score: Decimal, // extra budget points per reputation point
+    #[serde(default = "default_endorsement_budget_period")]
+    pub endorsement_budget_period: u64, // seconds between budget refills
+    #[serde(default = "default_reciprocal_discount")]
+    pub reciprocal_endorsement_discount: Decimal, // share of weight removed for A<->B endorsements
+    #[serde(default = "default_cycle_discount")]
+    pub cycle_endorsement_discount: Decimal, // share of weight removed for A->B->C->A rings
+    #[serde(default = "default_flag_collusion")]
+    pub flag_collusion: bool, // record detected rings for moderation
+    #[serde(default)]
+    pub strict_skill_endorsements: bool, // require endorsers to be qualified in mapped skills
+    #[serde(default = "default_qualified_endorsement_bonus")]
+    pub qualified_endorsement_bonus: Decimal, // extra weight share for qualified endorsers
+    #[serde(default)]
+    pub require_endorsement_acceptance: bool, // unsolicited endorsements count only once accepted
+    #[serde(default)]
+    pub score_floor: Option<SignedDecimal>, // lowest score a penalty can bring an account down to
+    #[serde(default)]
+    pub dispute_resolvers: Vec<Addr>, // accounts allowed to issue penalties from disputes
+    #[serde(default)]
+    pub guardians: Vec<Addr>, // accounts that can jointly move a lost identity
+    #[serde(default)]
+    pub guardian_threshold: u32, // guardian approvals needed to move an identity
+    #[serde(default = "default_auto_mint")]
+    pub auto_mint: bool, // mint skill NFTs from proofs that meet a platform's level rules
+    #[serde(default)]
+    pub onchain_metadata: bool, // render NFT images as on-chain SVG data URIs
+    #[serde(default)]
+    pub proof_validity: Option<u64>, // seconds a new proof stays valid; forever when unset
+    #[serde(default)]
+    pub minters: Vec<Addr>, // accounts besides the admin that may mint and revoke skill NFTs
+}
+
+pub fn default_endorsement_budget_base() -> u32 {
+    100
+}
+
+pub fn default_endorsement_budget_per_score() -> Decimal {
+    Decimal::one()
+}
+
+pub fn default_endorsement_budget_period() -> u64 {
+    7 * 24 * 60 * 60
+}
+
+pub fn default_reciprocal_discount() -> Decimal {
+    Decimal::percent(50)
+}
+
+pub fn default_cycle_discount() -> Decimal {
+    Decimal::percent(25)
+}
+
+pub fn default_flag_collusion() -> bool {
+    true
+}
+
+pub fn default_qualified_endorsement_bonus() -> Decimal {
+    Decimal::percent(50)
+}
+
+pub fn default_auto_mint() -> bool {
+    true
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -22,12 +90,24 @@ pub struct SkillProof {
     pub timestamp: u64,
     pub verified: bool,
     pub metadata: Option<String>,
+    #[serde(default)]
+    pub expires_at: Option<u64>, // proofs stop backing NFTs once their validity ends
+    #[serde(default)]
+    pub revoked_at: Option<u64>,
+    #[serde(default)]
+    pub revocation_reason: Option<String>,
+}
+
+impl SkillProof {
+    pub fn is_active(&self, now: u64) -> bool {
+        self.revoked_at.is_none() && self.expires_at.is_none_or(|expires_at| now < expires_at)
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct UserReputation {
     pub user: Addr,
-    pub score: i32,
+    pub score: SignedDecimal,
     pub total_proofs: u32,
     pub endorsements_received: u32,
     pub endorsements_given: u32,
@@ -44,6 +124,78 @@ pub struct Endorsement {
     pub message: String,
     pub weight: u32,
     pub timestamp: u64,
+    #[serde(default)]
+    pub status: EndorsementStatus,
+    #[serde(default)]
+    pub updated_at: Option<u64>,
+    #[serde(default)]
+    pub discount: Decimal, // collusion discount applied to the weight
+    #[serde(default)]
+    pub bonus: Decimal, // skill-qualification bonus applied to the weight
+}
+
+impl Endorsement {
+    // Weight that actually counts towards the endorsee's score
+    pub fn effective_weight(&self) -> Result<Decimal, OverflowError> {
+        Decimal::from_ratio(self.weight, 1u32)
+            .checked_mul(Decimal::one().checked_add(self.bonus)?)?
+            .checked_mul(Decimal::one().checked_sub(self.discount)?)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EndorsementStatus {
+    #[default]
+    Active,
+    Revoked,
+    Pending,   // awaiting the endorsee's acceptance
+    Declined,  // rejected by the endorsee
+    Withdrawn, // taken back by the endorser while still pending
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EndorsementRequestStatus {
+    Open,
+    Fulfilled,
+    Dismissed, // closed by the endorser without endorsing
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EndorsementRequest {
+    pub id: String,
+    pub requester: Addr, // the would-be endorsee
+    pub endorser: Addr,
+    pub skill: String,
+    pub message: Option<String>,
+    pub created_at: u64,
+    pub status: EndorsementRequestStatus,
+    pub endorsement_id: Option<String>, // set once fulfilled
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CollusionKind {
+    Reciprocal, // A->B while B->A
+    Cycle,      // A->B->C->A
+    Unchecked,  // the endorsee endorses more accounts than a ring check covers
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CollusionFlag {
+    pub user: Addr,
+    pub kinds: Vec<CollusionKind>,
+    pub counterparties: Vec<Addr>,
+    pub occurrences: u32,
+    pub first_flagged: u64,
+    pub last_flagged: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EndorsementBudget {
+    pub points: u32,      // points left in the current period
+    pub last_refill: u64, // start of the current period
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -56,6 +208,152 @@ pub struct SkillNft {
     pub created_at: u64,
     pub last_updated: u64,
     pub proof_count: u32, // number of proofs supporting this NFT level
+    #[serde(default)]
+    pub attestor: Option<Addr>, // account that verified the current level
+    #[serde(default)]
+    pub proof_ids: Vec<String>, // active proofs that earn the current level
+}
+
+// Verified platform statistics read from a proof's `skill_data`; other fields are ignored
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillData {
+    pub total_solutions: Option<u64>,
+    pub repositories: Option<u64>,
+    pub followers: Option<u64>,
+    pub stars: Option<u64>,
+    pub contributions: Option<u64>,
+    pub badges: Option<u64>,
+    pub score: Option<u64>,
+    pub rating: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SkillMetric {
+    TotalSolutions,
+    Repositories,
+    Followers,
+    Stars,
+    Contributions,
+    Badges,
+    Score,
+    Rating,
+}
+
+impl SkillData {
+    pub fn metric(&self, metric: SkillMetric) -> Option<u64> {
+        match metric {
+            SkillMetric::TotalSolutions => self.total_solutions,
+            SkillMetric::Repositories => self.repositories,
+            SkillMetric::Followers => self.followers,
+            SkillMetric::Stars => self.stars,
+            SkillMetric::Contributions => self.contributions,
+            SkillMetric::Badges => self.badges,
+            SkillMetric::Score => self.score,
+            SkillMetric::Rating => self.rating,
+        }
+    }
+}
+
+// Named after the `skill_data` field the metric is read from
+impl std::fmt::Display for SkillMetric {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            SkillMetric::TotalSolutions => write!(f, "totalSolutions"),
+            SkillMetric::Repositories => write!(f, "repositories"),
+            SkillMetric::Followers => write!(f, "followers"),
+            SkillMetric::Stars => write!(f, "stars"),
+            SkillMetric::Contributions => write!(f, "contributions"),
+            SkillMetric::Badges => write!(f, "badges"),
+            SkillMetric::Score => write!(f, "score"),
+            SkillMetric::Rating => write!(f, "rating"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LevelRule {
+    pub metric: SkillMetric,
+    pub thresholds: Vec<u64>, // ascending minimums for Bronze, Silver, Gold and Platinum
+}
+
+// `UserReputation` counter an achievement is measured on
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AchievementMetric {
+    TotalProofs,
+    Platforms,
+    EndorsementsReceived,
+    EndorsementsGiven,
+}
+
+impl UserReputation {
+    pub fn counter(&self, metric: AchievementMetric) -> u32 {
+        match metric {
+            AchievementMetric::TotalProofs => self.total_proofs,
+            AchievementMetric::Platforms => self.platforms.len() as u32,
+            AchievementMetric::EndorsementsReceived => self.endorsements_received,
+            AchievementMetric::EndorsementsGiven => self.endorsements_given,
+        }
+    }
+}
+
+// Milestone unlocked once `metric` reaches `threshold`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Achievement {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub metric: AchievementMetric,
+    pub threshold: u32,
+}
+
+// Soulbound record of an unlocked achievement; kept even if the counter drops again
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AchievementAward {
+    pub achievement_id: String,
+    pub user: Addr,
+    pub value: u32, // counter value when the achievement was unlocked
+    pub awarded_at: u64,
+    pub height: u64,
+}
+
+// Condition of a composite badge rule; nest `all_of` and `any_of` to combine them
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BadgeCondition {
+    // Holds the platform's skill NFT at `min_level` or above
+    NftLevel { platform: String, min_level: u32 },
+    // Has at least `count` active proofs on the platform
+    Proofs { platform: String, count: u32 },
+    // Has at least `count` active endorsements for the skill
+    Endorsements { skill: String, count: u32 },
+    AllOf { conditions: Vec<BadgeCondition> },
+    AnyOf { conditions: Vec<BadgeCondition> },
+}
+
+// Composite badge awarded as a skill NFT to every account meeting `condition`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BadgeRule {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub condition: BadgeCondition,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct NftLevelChange {
+    pub from_level: u32, // 0 when the token was minted
+    pub to_level: u32,
+    pub changed_at: u64,
+    pub proof_ids: Vec<String>, // proofs behind the change: those earning the new level, or the ones that lapsed
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CollectionInfo {
+    pub name: String,
+    pub symbol: String,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -63,18 +361,144 @@ pub struct PlatformStats {
     pub platform: String,
     pub total_users: u32,
     pub total_proofs: u32,
-    pub average_score: f64,
+    pub average_score: Decimal, // a decimal string on the wire; was an f64 number before 0.2.0
     pub top_users: Vec<Addr>,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ReputationTier {
+    pub name: String,
+    pub min_score: SignedDecimal, // lowest score that reaches this tier
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TrustJobPhase {
+    CountSeeds, // count proof-verified accounts and reset out-weights
+    Init,       // seed the working vector with the pre-trust distribution
+    OutWeights, // sum endorsement weight given by each endorser
+    Propagate,  // push trust along endorsement edges
+    Commit,     // fold the pre-trust share in and finish the iteration
+    Done,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TrustJob {
+    pub phase: TrustJobPhase,
+    pub cursor: Option<String>, // last key processed in the current phase
+    pub iteration: u32,
+    pub max_iterations: u32,
+    pub damping: Decimal,
+    pub seed_count: u32,
+    pub started_at: u64,
+    pub completed_at: Option<u64>,
+}
+
+#[derive(
+    Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum RestrictedAction {
+    Endorse,     // give, update, request or accept endorsements
+    SubmitProof, // store new skill proofs
+}
+
+impl std::fmt::Display for RestrictedAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            RestrictedAction::Endorse => write!(f, "endorse"),
+            RestrictedAction::SubmitProof => write!(f, "submit_proof"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Penalty {
+    pub id: u64,
+    pub user: Addr,
+    pub issued_by: Addr,
+    pub dispute_id: Option<String>, // set when issued by a dispute resolver
+    pub score_penalty: Decimal,     // points deducted when the penalty was issued
+    pub restrictions: Vec<RestrictedAction>,
+    pub reason: String,
+    pub created_at: u64,
+    pub expires_at: Option<u64>, // restrictions apply until then; None means until lifted
+    pub lifted_at: Option<u64>,
+}
+
+impl Penalty {
+    pub fn is_active(&self, now: u64) -> bool {
+        self.lifted_at.is_none() && self.expires_at.is_none_or(|expires_at| now < expires_at)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MerkleJobPhase {
+    Leaves, // hash every account's snapshot into the bottom level
+    Build,  // hash pairs of nodes into the level above
+    Prune,  // delete the previous epoch's leaves and nodes once the new root is published
+    Done,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MerkleJob {
+    pub epoch: u64,
+    pub height: u64, // reputations are read as of the start of this block
+    pub phase: MerkleJobPhase,
+    pub cursor: Option<String>, // last account hashed into a leaf
+    pub level: u32,             // level being hashed into the one above
+    pub level_size: u32,        // number of nodes on `level`
+    pub position: u32,          // next parent node to compute on `level + 1`
+    pub leaf_count: u32,
+    pub started_at: u64,
+    pub completed_at: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MerkleRoot {
+    pub epoch: u64,
+    pub root: String, // hex-encoded sha256
+    pub height: u64,
+    pub leaf_count: u32,
+    pub created_at: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MerkleLeaf {
+    pub index: u32,
+    pub score: SignedDecimal,
+    pub tier: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct IdentityMigration {
+    pub old_address: Addr,
+    pub new_address: Addr,
+    pub approvals: Vec<Addr>, // guardians that approved so far
+    pub proposed_at: u64,
+}
+
 // Storage
 pub const CONFIG: Item<Config> = Item::new("config");
 
 // proof_id -> SkillProof
 pub const PROOFS: Map<String, SkillProof> = Map::new("proofs");
 
-// user_addr -> UserReputation
-pub const REPUTATIONS: Map<&Addr, UserReputation> = Map::new("reputations");
+// (expires_at, proof_id) for proofs with a validity period, swept once they lapse
+pub const PROOF_EXPIRIES: Map<(u64, &str), Empty> = Map::new("proof_expiries");
+
+// user_addr -> UserReputation, with a changelog so past heights can be queried
+pub const REPUTATIONS: SnapshotMap<&Addr, UserReputation> = SnapshotMap::new(
+    "reputations",
+    "reputations__checkpoints",
+    "reputations__changelog",
+    Strategy::EveryBlock,
+);
+
+// Every address that ever held a reputation. Never pruned, so listing a past height
+// still finds accounts that have since moved to a new address.
+pub const REPUTATION_ACCOUNTS: Map<&Addr, Empty> = Map::new("reputation_accounts");
 
 // endorsement_id -> Endorsement
 pub const ENDORSEMENTS: Map<String, Endorsement> = Map::new("endorsements");
@@ -82,6 +506,18 @@ pub const ENDORSEMENTS: Map<String, Endorsement> = Map::new("endorsements");
 // token_id -> SkillNft
 pub const SKILL_NFTS: Map<String, SkillNft> = Map::new("skill_nfts");
 
+// platform -> LevelRule list; a proof earns the highest level any rule grants
+pub const LEVEL_RULES: Map<&str, Vec<LevelRule>> = Map::new("level_rules");
+
+// (platform, level) -> SVG badge template; level 0 applies to every level of the platform
+pub const SVG_TEMPLATES: Map<(&str, u32), String> = Map::new("svg_templates");
+
+// cw721 collection name and symbol
+pub const COLLECTION_INFO: Item<CollectionInfo> = Item::new("collection_info");
+
+// Number of SkillNft tokens in existence
+pub const NUM_TOKENS: Item<u64> = Item::new("num_tokens");
+
 // platform -> PlatformStats
 pub const PLATFORM_STATS: Map<String, PlatformStats> = Map::new("platform_stats");
 
@@ -92,11 +528,205 @@ pub const USER_PROOFS: Map<&Addr, Vec<String>> = Map::new("user_proofs");
 // user_addr -> Vec<endorsement_id>
 pub const USER_ENDORSEMENTS: Map<&Addr, Vec<String>> = Map::new("user_endorsements");
 
+// endorser_addr -> Vec<endorsement_id> (all endorsements given, oldest first)
+pub const USER_ENDORSEMENTS_GIVEN: Map<&Addr, Vec<String>> = Map::new("user_endorsements_given");
+
+// user_addr -> Vec<endorsement_id> (revoked endorsements received, kept for history)
+pub const USER_REVOKED_ENDORSEMENTS: Map<&Addr, Vec<String>> =
+    Map::new("user_revoked_endorsements");
+
+// user_addr -> Vec<endorsement_id> (declined endorsements received, kept for the cooldown)
+pub const USER_DECLINED_ENDORSEMENTS: Map<&Addr, Vec<String>> =
+    Map::new("user_declined_endorsements");
+
+// user_addr -> Vec<endorsement_id> (pending endorsements withdrawn by their endorser, kept
+// for the cooldown)
+pub const USER_WITHDRAWN_ENDORSEMENTS: Map<&Addr, Vec<String>> =
+    Map::new("user_withdrawn_endorsements");
+
+// skill -> Vec<platform> whose proofs or NFTs qualify an endorser for it
+pub const SKILL_PLATFORMS: Map<&str, Vec<String>> = Map::new("skill_platforms");
+
+// (endorser, endorsee, skill) -> latest endorsement_id
+pub const ENDORSEMENT_PAIRS: Map<(&Addr, &Addr, &str), String> = Map::new("endorsement_pairs");
+
+// (endorser, endorsee) -> skills the endorser actively endorses the endorsee for
+pub const ACTIVE_ENDORSEMENT_EDGES: Map<(&Addr, &Addr), u64> = Map::new("active_endorsement_edges");
+
+// endorser_addr -> EndorsementBudget
+pub const ENDORSEMENT_BUDGETS: Map<&Addr, EndorsementBudget> = Map::new("endorsement_budgets");
+
+// user_addr -> CollusionFlag (accounts caught in endorsement rings)
+pub const COLLUSION_FLAGS: Map<&Addr, CollusionFlag> = Map::new("collusion_flags");
+
+// user_addr -> Vec<endorsement_id> (endorsements awaiting the user's acceptance)
+pub const PENDING_ENDORSEMENTS: Map<&Addr, Vec<String>> = Map::new("pending_endorsements");
+
+// request_id -> EndorsementRequest
+pub const ENDORSEMENT_REQUESTS: Map<String, EndorsementRequest> = Map::new("endorsement_requests");
+
+// (endorser, requester, skill) -> open request_id
+pub const OPEN_ENDORSEMENT_REQUESTS: Map<(&Addr, &Addr, &str), String> =
+    Map::new("open_endorsement_requests");
+
+// requester_addr -> Vec<request_id> (open requests sent)
+pub const REQUESTS_SENT: Map<&Addr, Vec<String>> = Map::new("requests_sent");
+
+// endorser_addr -> Vec<request_id> (open requests received)
+pub const REQUESTS_RECEIVED: Map<&Addr, Vec<String>> = Map::new("requests_received");
+
+// (owner_addr, platform) -> token_id of the owner's NFT for that platform
+pub const OWNER_PLATFORM_NFTS: Map<(&Addr, &str), String> = Map::new("owner_platform_nfts");
+
+// (owner_addr, platform) -> time the owner burned their NFT for that platform. Proofs do
+// not mint it again until the owner claims it or the NFT authority mints it.
+pub const BURNED_NFTS: Map<(&Addr, &str), u64> = Map::new("burned_nfts");
+
+// (owner_addr, platform) -> time the NFT authority revoked the owner's NFT for that platform.
+// Proofs, claims and badge rules do not award it again until the admin mints it.
+pub const REVOKED_NFTS: Map<(&Addr, &str), u64> = Map::new("revoked_nfts");
+
+// achievement_id -> Achievement
+pub const ACHIEVEMENTS: Map<&str, Achievement> = Map::new("achievements");
+
+// (user_addr, achievement_id) -> AchievementAward
+pub const USER_ACHIEVEMENTS: Map<(&Addr, &str), AchievementAward> = Map::new("user_achievements");
+
+// badge_id -> BadgeRule
+pub const BADGE_RULES: Map<&str, BadgeRule> = Map::new("badge_rules");
+
+// token_id -> NftLevelChange list, oldest first
+pub const NFT_LEVEL_HISTORY: Map<&str, Vec<NftLevelChange>> = Map::new("nft_level_history");
+
 // user_addr -> Vec<token_id>
 pub const USER_NFTS: Map<&Addr, Vec<String>> = Map::new("user_nfts");
 
-// platform -> Vec<user_addr> (sorted by score for leaderboard)
-pub const PLATFORM_LEADERBOARDS: Map<String, Vec<Addr>> = Map::new("platform_leaderboards");
+// Leaderboard indexes over accounts verified on at least one platform, ordered by
+// `score_key(score)` so the top scores are read in descending key order
+// (score_key, user_addr)
+pub const GLOBAL_SCORE_INDEX: Map<(u128, &Addr), Empty> = Map::new("global_score_index");
+// (platform, score_key, user_addr)
+pub const PLATFORM_SCORE_INDEX: Map<(&str, u128, &Addr), Empty> = Map::new("platform_score_index");
+
+// Number of accounts on the global and on each platform leaderboard
+pub const GLOBAL_LEADERBOARD_SIZE: Item<u64> = Item::new("global_leaderboard_size");
+pub const PLATFORM_LEADERBOARD_SIZES: Map<&str, u64> = Map::new("platform_leaderboard_sizes");
+
+// Sortable index key for a score: the two's-complement bits with the sign bit flipped
+pub fn score_key(score: SignedDecimal) -> u128 {
+    (score.atomics().i128() as u128) ^ (1 << 127)
+}
+
+// Number of accounts under each score key prefix, so ranks are counted without walking
+// the accounts above. The counts form a tree over the key's bytes: level n holds the
+// accounts per n-byte prefix, and the last level the accounts at each exact score.
+// Counting the accounts above a score reads at most 255 siblings per level, whatever
+// the number of accounts or how spread out their scores are.
+// (level, score_prefix(score_key, level)) -> accounts
+pub const GLOBAL_SCORE_COUNTS: Map<(u8, u128), u64> = Map::new("global_score_prefix_counts");
+// (platform, level, score_prefix(score_key, level)) -> accounts
+pub const PLATFORM_SCORE_COUNTS: Map<(&str, u8, u128), u64> =
+    Map::new("platform_score_prefix_counts");
+
+pub const SCORE_PREFIX_LEVELS: u8 = 16;
+
+// Leading `level` bytes of a score key
+pub fn score_prefix(key: u128, level: u8) -> u128 {
+    key >> (8 * (SCORE_PREFIX_LEVELS - level) as u32)
+}
+
+// Reputation tiers, ordered by ascending min_score, with a changelog so past heights
+// resolve against the thresholds in force at the time
+pub const REPUTATION_TIERS: SnapshotItem<Vec<ReputationTier>> = SnapshotItem::new(
+    "reputation_tiers",
+    "reputation_tiers__checkpoints",
+    "reputation_tiers__changelog",
+    Strategy::EveryBlock,
+);
+
+// Trust propagation job state (resumable across transactions)
+pub const TRUST_JOB: Item<TrustJob> = Item::new("trust_job");
+
+// user_addr -> published trust score from the last completed job
+pub const TRUST_SCORES: Map<&Addr, Decimal> = Map::new("trust_scores");
+
+// Working vectors used while a trust job is running
+// user_addr -> trust value of the previous iteration
+pub const TRUST_CURRENT: Map<&Addr, Decimal> = Map::new("trust_current");
+// user_addr -> trust accumulated for the next iteration
+pub const TRUST_NEXT: Map<&Addr, Decimal> = Map::new("trust_next");
+// endorser_addr -> total endorsement weight given
+pub const TRUST_OUT_WEIGHTS: Map<&Addr, Decimal> = Map::new("trust_out_weights");
+
+// Merkle export job state (resumable across transactions)
+pub const MERKLE_JOB: Item<MerkleJob> = Item::new("merkle_job");
+
+// epoch -> MerkleRoot of a completed export
+pub const MERKLE_ROOTS: Map<u64, MerkleRoot> = Map::new("merkle_roots");
+
+// Nodes and leaves are only kept for the latest epoch; the next export prunes them once its
+// own root is published
+// (epoch, level, index) -> hex-encoded node hash; level 0 holds the leaves
+pub const MERKLE_NODES: Map<(u64, u32, u32), String> = Map::new("merkle_nodes");
+
+// (epoch, user_addr) -> exported leaf data
+pub const MERKLE_LEAVES: Map<(u64, &Addr), MerkleLeaf> = Map::new("merkle_leaves");
+
+// old_addr -> IdentityMigration awaiting guardian approvals
+pub const IDENTITY_MIGRATIONS: Map<&Addr, IdentityMigration> = Map::new("identity_migrations");
+
+// Last issued penalty id
+pub const PENALTY_COUNT: Item<u64> = Item::new("penalty_count");
+
+// penalty_id -> Penalty
+pub const PENALTIES: Map<u64, Penalty> = Map::new("penalties");
+
+// user_addr -> Vec<penalty_id> (all penalties issued, oldest first)
+pub const USER_PENALTIES: Map<&Addr, Vec<u64>> = Map::new("user_penalties");
+
+// Scores written before reputation moved to fixed-point decimals were plain integers.
+// The legacy views below read either shape so `migrate` can rewrite old records.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(untagged)]
+pub enum StoredScore {
+    Integer(i64),
+    Decimal(SignedDecimal),
+}
+
+impl From<StoredScore> for SignedDecimal {
+    fn from(score: StoredScore) -> Self {
+        match score {
+            StoredScore::Integer(points) => SignedDecimal::from_ratio(points, 1),
+            StoredScore::Decimal(score) => score,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyUserReputation {
+    pub user: Addr,
+    pub score: StoredScore,
+    pub total_proofs: u32,
+    pub endorsements_received: u32,
+    pub endorsements_given: u32,
+    pub last_updated: u64,
+    pub platforms: Vec<String>,
+}
+
+pub const LEGACY_REPUTATIONS: Map<&Addr, LegacyUserReputation> = Map::new("reputations");
+
+// Platform stats stored their average as a float, which was never computed; the
+// legacy view skips that field so `migrate` can rewrite it as a decimal.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyPlatformStats {
+    pub platform: String,
+    pub total_users: u32,
+    pub total_proofs: u32,
+    pub top_users: Vec<Addr>,
+}
+
+pub const LEGACY_PLATFORM_STATS: Map<String, LegacyPlatformStats> = Map::new("platform_stats");
 
-// Global leaderboard (all platforms combined)
-pub const GLOBAL_LEADERBOARD: Item<Vec<Addr>> = Item::new("global_leaderboard");
+// Insertion-ordered leaderboards replaced by the score indexes; only cleared by `migrate`
+pub const LEGACY_PLATFORM_LEADERBOARDS: Map<String, Vec<Addr>> = Map::new("platform_leaderboards");
+pub const LEGACY_GLOBAL_LEADERBOARD: Item<Vec<Addr>> = Item::new("global_leaderboard");
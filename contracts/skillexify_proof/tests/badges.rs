@@ -0,0 +1,41 @@
+mod common;
+
+use common::{attribute, exec, has_event, setup, store_proof, user_nfts, Deps};
+use cosmwasm_std::{from_json, Env};
+use skillexify_proof::msg::ExecuteMsg;
+
+fn refresh_badges(deps: &mut Deps, env: &Env, user: &str) -> bool {
+    let msg = ExecuteMsg::RefreshBadges {
+        user: user.to_string(),
+    };
+    let res = exec(deps, env, "carol", msg).unwrap();
+    has_event(&res, "badge_awarded")
+}
+
+#[test]
+fn new_badge_rules_wait_for_a_refresh() {
+    let (mut deps, mut env) = setup();
+    store_proof(&mut deps, &mut env, "alice", "LeetCode", "{}");
+
+    let msg = from_json(
+        r#"{"set_badge_rule":{"rule":{"id":"coder","name":"Coder","description":"d",
+            "condition":{"proofs":{"platform":"LeetCode","count":1}}}}}"#,
+    )
+    .unwrap();
+    let res = exec(&mut deps, &env, "admin", msg).unwrap();
+    assert_eq!(attribute(&res, "evaluation"), "deferred");
+    assert!(user_nfts(&deps, &env, "alice").is_empty());
+
+    assert!(refresh_badges(&mut deps, &env, "alice"));
+    let nfts = user_nfts(&deps, &env, "alice");
+    assert_eq!(nfts[0].token_id, "alice:badge:coder");
+
+    // A burned badge stays burned while its condition still holds
+    let msg = ExecuteMsg::BurnSkillNft {
+        token_id: nfts[0].token_id.clone(),
+    };
+    exec(&mut deps, &env, "alice", msg).unwrap();
+    assert!(!refresh_badges(&mut deps, &env, "alice"));
+    store_proof(&mut deps, &mut env, "alice", "LeetCode", "{}");
+    assert!(user_nfts(&deps, &env, "alice").is_empty());
+}
@@ -0,0 +1,201 @@
+mod common;
+
+use common::{attribute, endorse, exec, onboard, query_as, setup, update_config, Deps};
+use cosmwasm_std::{Decimal, Env, Response};
+use skillexify_proof::msg::{EndorsementResponse, ExecuteMsg, QueryMsg};
+use skillexify_proof::state::CollusionFlag;
+use skillexify_proof::ContractError;
+
+fn collusion_flags(deps: &Deps, env: &Env) -> Vec<(String, Vec<String>)> {
+    let flags: Vec<CollusionFlag> = query_as(
+        deps,
+        env,
+        QueryMsg::GetCollusionFlags {
+            start_after: None,
+            limit: None,
+        },
+    );
+    flags
+        .into_iter()
+        .map(|flag| {
+            let mut counterparties: Vec<_> = flag
+                .counterparties
+                .into_iter()
+                .map(|addr| addr.to_string())
+                .collect();
+            counterparties.sort();
+            (flag.user.to_string(), counterparties)
+        })
+        .collect()
+}
+
+fn flagged_users(deps: &Deps, env: &Env, start_after: Option<&str>, limit: u32) -> Vec<String> {
+    let flags: Vec<CollusionFlag> = query_as(
+        deps,
+        env,
+        QueryMsg::GetCollusionFlags {
+            start_after: start_after.map(str::to_string),
+            limit: Some(limit),
+        },
+    );
+    flags
+        .into_iter()
+        .map(|flag| flag.user.to_string())
+        .collect()
+}
+
+fn effective_weight(deps: &Deps, env: &Env, endorsement_id: String) -> Decimal {
+    let endorsement: EndorsementResponse =
+        query_as(deps, env, QueryMsg::GetEndorsement { endorsement_id });
+    endorsement.effective_weight
+}
+
+fn accept(deps: &mut Deps, env: &Env, endorsee: &str, endorsement_id: String) -> Response {
+    let msg = ExecuteMsg::AcceptEndorsement { endorsement_id };
+    exec(deps, env, endorsee, msg).unwrap()
+}
+
+#[test]
+fn accepting_a_pending_endorsement_checks_for_rings() {
+    let (mut deps, mut env) = setup();
+    update_config(
+        &mut deps,
+        &env,
+        r#"{"require_endorsement_acceptance":true}"#,
+    );
+    onboard(&mut deps, &mut env, "alice");
+    onboard(&mut deps, &mut env, "bob");
+
+    // Both edges wait for acceptance, so neither closes a ring yet
+    let res = endorse(&mut deps, &env, "alice", "bob", "Rust").unwrap();
+    assert_eq!(attribute(&res, "collusion"), "none");
+    let alice_to_bob = attribute(&res, "endorsement_id");
+    let res = endorse(&mut deps, &env, "bob", "alice", "Rust").unwrap();
+    assert_eq!(attribute(&res, "collusion"), "none");
+    let bob_to_alice = attribute(&res, "endorsement_id");
+
+    let res = accept(&mut deps, &env, "alice", bob_to_alice);
+    assert_eq!(attribute(&res, "collusion"), "none");
+    assert!(collusion_flags(&deps, &env).is_empty());
+
+    // The second acceptance closes the pair
+    let res = accept(&mut deps, &env, "bob", alice_to_bob.clone());
+    assert_eq!(attribute(&res, "collusion"), "reciprocal");
+    assert_eq!(
+        effective_weight(&deps, &env, alice_to_bob),
+        Decimal::from_ratio(5u32, 1u32)
+    );
+    assert_eq!(
+        collusion_flags(&deps, &env),
+        vec![
+            ("alice".to_string(), vec!["bob".to_string()]),
+            ("bob".to_string(), vec!["alice".to_string()]),
+        ]
+    );
+}
+
+#[test]
+fn reciprocal_endorsements_are_discounted() {
+    let (mut deps, mut env) = setup();
+    onboard(&mut deps, &mut env, "alice");
+    onboard(&mut deps, &mut env, "bob");
+
+    let res = endorse(&mut deps, &env, "alice", "bob", "Rust").unwrap();
+    assert_eq!(attribute(&res, "collusion"), "none");
+    let res = endorse(&mut deps, &env, "bob", "alice", "Go").unwrap();
+    assert_eq!(attribute(&res, "collusion"), "reciprocal");
+    assert_eq!(attribute(&res, "effective_weight"), "5");
+    assert_eq!(flagged_users(&deps, &env, None, 10), vec!["alice", "bob"]);
+}
+
+#[test]
+fn cycles_are_found_within_the_ring_scan() {
+    let (mut deps, mut env) = setup();
+    update_config(&mut deps, &env, r#"{"endorsement_budget_base":10000}"#);
+    for user in ["alice", "carol", "dave", "erin", "frank", "zoe"] {
+        onboard(&mut deps, &mut env, user);
+    }
+
+    // Ahead of dave in key order, carol endorses one account for many skills and many
+    // accounts she then revokes; only the accounts she still endorses are scanned
+    for skill in 0..60 {
+        endorse(&mut deps, &env, "carol", "bob", &format!("skill{}", skill)).unwrap();
+    }
+    for account in 0..60 {
+        let res = endorse(
+            &mut deps,
+            &env,
+            "carol",
+            &format!("acct{:02}", account),
+            "Rust",
+        );
+        let endorsement_id = attribute(&res.unwrap(), "endorsement_id");
+        let msg = ExecuteMsg::RevokeEndorsement { endorsement_id };
+        exec(&mut deps, &env, "carol", msg).unwrap();
+    }
+
+    endorse(&mut deps, &env, "carol", "dave", "Rust").unwrap();
+    endorse(&mut deps, &env, "dave", "alice", "Rust").unwrap();
+    let res = endorse(&mut deps, &env, "alice", "carol", "Rust").unwrap();
+    assert_eq!(attribute(&res, "collusion"), "cycle");
+
+    // An endorsee actively endorsing more accounts than the scan covers is flagged for
+    // review rather than cleared
+    for account in 0..50 {
+        endorse(
+            &mut deps,
+            &env,
+            "erin",
+            &format!("acct{:02}", account),
+            "Rust",
+        )
+        .unwrap();
+    }
+    endorse(&mut deps, &env, "erin", "zoe", "Rust").unwrap();
+    endorse(&mut deps, &env, "zoe", "frank", "Rust").unwrap();
+    let res = endorse(&mut deps, &env, "frank", "erin", "Rust").unwrap();
+    assert_eq!(attribute(&res, "collusion"), "unchecked");
+
+    assert_eq!(
+        collusion_flags(&deps, &env),
+        vec![
+            (
+                "alice".to_string(),
+                vec!["carol".to_string(), "dave".to_string()]
+            ),
+            (
+                "carol".to_string(),
+                vec!["alice".to_string(), "dave".to_string()]
+            ),
+            (
+                "dave".to_string(),
+                vec!["alice".to_string(), "carol".to_string()]
+            ),
+            ("erin".to_string(), vec!["frank".to_string()]),
+            ("frank".to_string(), vec!["erin".to_string()]),
+        ]
+    );
+}
+
+#[test]
+fn moderators_page_through_and_clear_flags() {
+    let (mut deps, mut env) = setup();
+    for user in ["alice", "bob", "carol"] {
+        onboard(&mut deps, &mut env, user);
+    }
+    endorse(&mut deps, &env, "alice", "bob", "Rust").unwrap();
+    endorse(&mut deps, &env, "bob", "alice", "Rust").unwrap();
+    endorse(&mut deps, &env, "carol", "bob", "Rust").unwrap();
+    endorse(&mut deps, &env, "bob", "carol", "Rust").unwrap();
+
+    assert_eq!(flagged_users(&deps, &env, None, 2), vec!["alice", "bob"]);
+    assert_eq!(flagged_users(&deps, &env, Some("bob"), 2), vec!["carol"]);
+
+    let msg = ExecuteMsg::ClearCollusionFlag {
+        user: "bob".to_string(),
+    };
+    let err = exec(&mut deps, &env, "carol", msg.clone()).unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+    exec(&mut deps, &env, "admin", msg).unwrap();
+    assert_eq!(flagged_users(&deps, &env, None, 10), vec!["alice", "carol"]);
+}
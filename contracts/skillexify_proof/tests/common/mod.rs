@@ -0,0 +1,111 @@
+// Helpers shared by the integration test crates; each crate uses a different subset
+#![allow(dead_code)]
+
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{from_json, Env, OwnedDeps, Response};
+use serde::de::DeserializeOwned;
+use skillexify_proof::contract::{execute, instantiate, query};
+use skillexify_proof::msg::{ExecuteMsg, InstantiateMsg, NftResponse, QueryMsg};
+use skillexify_proof::ContractError;
+
+pub type Deps = OwnedDeps<MockStorage, MockApi, MockQuerier>;
+
+pub const PROOF_HASH: &str = "0123456789abcdef0123456789abcdef";
+
+pub fn setup() -> (Deps, Env) {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let msg = InstantiateMsg {
+        admin: None,
+        name: None,
+        symbol: None,
+    };
+    instantiate(deps.as_mut(), env.clone(), mock_info("admin", &[]), msg).unwrap();
+    (deps, env)
+}
+
+pub fn exec(
+    deps: &mut Deps,
+    env: &Env,
+    sender: &str,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    execute(deps.as_mut(), env.clone(), mock_info(sender, &[]), msg)
+}
+
+pub fn query_as<T: DeserializeOwned>(deps: &Deps, env: &Env, msg: QueryMsg) -> T {
+    from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap()
+}
+
+// Apply a partial config update written as JSON, e.g. `{"endorsement_cooldown":60}`
+pub fn update_config(deps: &mut Deps, env: &Env, update: &str) {
+    let msg = from_json(format!(r#"{{"update_config":{}}}"#, update)).unwrap();
+    exec(deps, env, "admin", msg).unwrap();
+}
+
+// Store a proof one block after the previous one so proof ids stay unique
+pub fn store_proof(
+    deps: &mut Deps,
+    env: &mut Env,
+    user: &str,
+    platform: &str,
+    skill_data: &str,
+) -> String {
+    env.block.time = env.block.time.plus_seconds(1);
+    env.block.height += 1;
+    let msg = ExecuteMsg::StoreProof {
+        platform: platform.to_string(),
+        username: user.to_string(),
+        skill_data: skill_data.to_string(),
+        proof_hash: PROOF_HASH.to_string(),
+        metadata: None,
+    };
+    let res = exec(deps, env, user, msg).unwrap();
+    attribute(&res, "proof_id")
+}
+
+// Give the user proofs on two platforms, enough reputation to endorse others
+pub fn onboard(deps: &mut Deps, env: &mut Env, user: &str) {
+    store_proof(deps, env, user, "LeetCode", "{}");
+    store_proof(deps, env, user, "GitHub", "{}");
+}
+
+pub fn endorse(
+    deps: &mut Deps,
+    env: &Env,
+    endorser: &str,
+    endorsee: &str,
+    skill: &str,
+) -> Result<Response, ContractError> {
+    let msg = ExecuteMsg::AddEndorsement {
+        endorsee: endorsee.to_string(),
+        skill: skill.to_string(),
+        message: "solid work".to_string(),
+        weight: 10,
+    };
+    exec(deps, env, endorser, msg)
+}
+
+pub fn attribute(res: &Response, key: &str) -> String {
+    res.attributes
+        .iter()
+        .find(|attr| attr.key == key)
+        .map(|attr| attr.value.clone())
+        .unwrap_or_else(|| panic!("missing attribute {}", key))
+}
+
+pub fn has_event(res: &Response, ty: &str) -> bool {
+    res.events.iter().any(|event| event.ty == ty)
+}
+
+pub fn user_nfts(deps: &Deps, env: &Env, user: &str) -> Vec<NftResponse> {
+    query_as(
+        deps,
+        env,
+        QueryMsg::GetUserNfts {
+            user: user.to_string(),
+        },
+    )
+}
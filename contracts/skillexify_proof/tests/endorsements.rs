@@ -0,0 +1,242 @@
+mod common;
+
+use common::{
+    attribute, endorse, exec, onboard, query_as, setup, store_proof, update_config, Deps,
+};
+use cosmwasm_std::{from_json, Env};
+use skillexify_proof::contract::query;
+use skillexify_proof::msg::{EndorsementRequestRole, EndorsementResponse, ExecuteMsg, QueryMsg};
+use skillexify_proof::state::{EndorsementRequest, EndorsementStatus};
+use skillexify_proof::ContractError;
+
+fn given(start_after: Option<&str>) -> QueryMsg {
+    QueryMsg::GetEndorsementsGiven {
+        user: "alice".to_string(),
+        skill: None,
+        start_after: start_after.map(str::to_string),
+        limit: Some(1),
+    }
+}
+
+#[test]
+fn endorsements_given_reject_foreign_cursors() {
+    let (mut deps, mut env) = setup();
+    onboard(&mut deps, &mut env, "alice");
+    onboard(&mut deps, &mut env, "bob");
+    for endorsee in ["bob", "carol"] {
+        endorse(&mut deps, &env, "alice", endorsee, "Rust").unwrap();
+    }
+    endorse(&mut deps, &env, "bob", "carol", "Rust").unwrap();
+
+    let first: Vec<EndorsementResponse> = query_as(&deps, &env, given(None));
+    let second: Vec<EndorsementResponse> = query_as(&deps, &env, given(Some(&first[0].id)));
+    assert_eq!(second[0].endorsee.as_str(), "carol");
+
+    // Bob's endorsement is not in Alice's index, so it cannot resume her listing
+    let bobs: Vec<EndorsementResponse> = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetEndorsementsGiven {
+            user: "bob".to_string(),
+            skill: None,
+            start_after: None,
+            limit: None,
+        },
+    );
+    let err = query(deps.as_ref(), env.clone(), given(Some(&bobs[0].id)));
+    assert!(err
+        .unwrap_err()
+        .to_string()
+        .contains("was not given by alice"));
+}
+
+#[test]
+fn cooldown_runs_from_the_revocation() {
+    let (mut deps, mut env) = setup();
+    update_config(&mut deps, &env, r#"{"endorsement_cooldown":100}"#);
+    onboard(&mut deps, &mut env, "alice");
+    let res = endorse(&mut deps, &env, "alice", "bob", "Rust").unwrap();
+    let endorsement_id = attribute(&res, "endorsement_id");
+
+    env.block.time = env.block.time.plus_seconds(90);
+    let revoked_at = env.block.time.seconds();
+    let msg = ExecuteMsg::RevokeEndorsement { endorsement_id };
+    exec(&mut deps, &env, "alice", msg).unwrap();
+
+    // Past the cooldown counted from the original endorsement, but not from the revocation
+    env.block.time = env.block.time.plus_seconds(20);
+    let err = endorse(&mut deps, &env, "alice", "bob", "Rust").unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::EndorsementCooldown { available_at } if available_at == revoked_at + 100
+    ));
+
+    env.block.time = env.block.time.plus_seconds(80);
+    endorse(&mut deps, &env, "alice", "bob", "Rust").unwrap();
+}
+
+#[test]
+fn qualified_endorsement_bonus_is_bounded() {
+    let (mut deps, env) = setup();
+    let msg = from_json(r#"{"update_config":{"qualified_endorsement_bonus":"1.5"}}"#).unwrap();
+    let err = exec(&mut deps, &env, "admin", msg).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidBonus { .. }));
+    update_config(&mut deps, &env, r#"{"qualified_endorsement_bonus":"1"}"#);
+}
+
+#[test]
+fn lapsed_proofs_and_their_nfts_stop_qualifying_endorsers() {
+    let (mut deps, mut env) = setup();
+    let msg = ExecuteMsg::SetSkillPlatforms {
+        skill: "Algorithms".to_string(),
+        platforms: vec!["LeetCode".to_string()],
+    };
+    exec(&mut deps, &env, "admin", msg).unwrap();
+    store_proof(&mut deps, &mut env, "alice", "GitHub", "{}");
+    update_config(&mut deps, &env, r#"{"proof_validity":100}"#);
+    store_proof(
+        &mut deps,
+        &mut env,
+        "alice",
+        "LeetCode",
+        r#"{"totalSolutions":160}"#,
+    );
+
+    let res = endorse(&mut deps, &env, "alice", "bob", "Algorithms").unwrap();
+    assert_eq!(attribute(&res, "qualified"), "true");
+
+    // The proof lapsed and nobody processed it; the NFT it backed is still stored
+    env.block.time = env.block.time.plus_seconds(100);
+    let res = endorse(&mut deps, &env, "alice", "carol", "Algorithms").unwrap();
+    assert_eq!(attribute(&res, "qualified"), "false");
+}
+
+fn open_requests(deps: &Deps, env: &Env, user: &str, role: EndorsementRequestRole) -> Vec<String> {
+    let requests: Vec<EndorsementRequest> = query_as(
+        deps,
+        env,
+        QueryMsg::GetEndorsementRequests {
+            user: user.to_string(),
+            role,
+        },
+    );
+    requests.into_iter().map(|request| request.id).collect()
+}
+
+fn request(
+    deps: &mut Deps,
+    env: &Env,
+    requester: &str,
+    endorser: &str,
+    skill: &str,
+) -> Result<String, ContractError> {
+    let msg = ExecuteMsg::RequestEndorsement {
+        endorser: endorser.to_string(),
+        skill: skill.to_string(),
+        message: None,
+    };
+    exec(deps, env, requester, msg).map(|res| attribute(&res, "request_id"))
+}
+
+#[test]
+fn endorsers_dismiss_requests_and_requesters_are_capped() {
+    let (mut deps, env) = setup();
+    let request_id = request(&mut deps, &env, "alice", "bob", "Rust").unwrap();
+
+    let msg = ExecuteMsg::DismissEndorsementRequest {
+        request_id: request_id.clone(),
+    };
+    let err = exec(&mut deps, &env, "alice", msg.clone()).unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+    exec(&mut deps, &env, "bob", msg.clone()).unwrap();
+    let err = exec(&mut deps, &env, "bob", msg).unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::EndorsementRequestClosed { .. }
+    ));
+    assert!(open_requests(&deps, &env, "bob", EndorsementRequestRole::Endorser).is_empty());
+    assert!(open_requests(&deps, &env, "alice", EndorsementRequestRole::Requester).is_empty());
+
+    // Open requests are capped per requester; closing one frees a slot
+    let mut request_ids = vec![];
+    for skill in 0..20 {
+        let skill = format!("skill{}", skill);
+        request_ids.push(request(&mut deps, &env, "alice", "bob", &skill).unwrap());
+    }
+    let err = request(&mut deps, &env, "alice", "carol", "Rust").unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::TooManyEndorsementRequests { limit: 20 }
+    ));
+    let msg = ExecuteMsg::DismissEndorsementRequest {
+        request_id: request_ids[0].clone(),
+    };
+    exec(&mut deps, &env, "bob", msg).unwrap();
+    request(&mut deps, &env, "alice", "carol", "Rust").unwrap();
+}
+
+#[test]
+fn open_requests_are_capped_per_endorser() {
+    let (mut deps, env) = setup();
+    let mut request_ids = vec![];
+    for requester in 0..50 {
+        let requester = format!("requester{}", requester);
+        request_ids.push(request(&mut deps, &env, &requester, "bob", "Rust").unwrap());
+    }
+    let err = request(&mut deps, &env, "alice", "bob", "Rust").unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::EndorserRequestsFull { limit: 50 }
+    ));
+    assert_eq!(
+        open_requests(&deps, &env, "bob", EndorsementRequestRole::Endorser).len(),
+        50
+    );
+
+    // Dismissing a request makes room for another
+    let msg = ExecuteMsg::DismissEndorsementRequest {
+        request_id: request_ids[0].clone(),
+    };
+    exec(&mut deps, &env, "bob", msg).unwrap();
+    request(&mut deps, &env, "alice", "bob", "Rust").unwrap();
+}
+
+#[test]
+fn withdrawn_pending_endorsements_stay_off_the_profile() {
+    let (mut deps, mut env) = setup();
+    update_config(
+        &mut deps,
+        &env,
+        r#"{"require_endorsement_acceptance":true}"#,
+    );
+    onboard(&mut deps, &mut env, "alice");
+    let res = endorse(&mut deps, &env, "alice", "bob", "Rust").unwrap();
+    let endorsement_id = attribute(&res, "endorsement_id");
+
+    let msg = ExecuteMsg::RevokeEndorsement {
+        endorsement_id: endorsement_id.clone(),
+    };
+    let res = exec(&mut deps, &env, "alice", msg).unwrap();
+    assert_eq!(attribute(&res, "status"), "withdrawn");
+    let endorsement: EndorsementResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetEndorsement {
+            endorsement_id: endorsement_id.clone(),
+        },
+    );
+    assert_eq!(endorsement.status, EndorsementStatus::Withdrawn);
+    let received: Vec<EndorsementResponse> = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetEndorsements {
+            user: "bob".to_string(),
+            skill: None,
+        },
+    );
+    assert!(received.is_empty());
+
+    let msg = ExecuteMsg::AcceptEndorsement { endorsement_id };
+    let err = exec(&mut deps, &env, "bob", msg).unwrap_err();
+    assert!(matches!(err, ContractError::EndorsementNotPending { .. }));
+}
@@ -0,0 +1,185 @@
+mod common;
+
+use common::{
+    attribute, endorse, exec, onboard, query_as, setup, store_proof, update_config, user_nfts,
+};
+use skillexify_proof::contract::query;
+use skillexify_proof::msg::{
+    ExecuteMsg, LeaderboardEntry, ProofResponse, QueryMsg, ReputationResponse,
+};
+use skillexify_proof::state::{CollusionFlag, PlatformStats};
+use skillexify_proof::ContractError;
+
+#[test]
+fn migrate_identity_moves_history() {
+    let (mut deps, mut env) = setup();
+    let proof_id = store_proof(
+        &mut deps,
+        &mut env,
+        "alice",
+        "LeetCode",
+        r#"{"totalSolutions":60}"#,
+    );
+    store_proof(&mut deps, &mut env, "bob", "LeetCode", "{}");
+
+    let msg = ExecuteMsg::MigrateIdentity {
+        old_address: "alice".to_string(),
+        new_address: "alice2".to_string(),
+    };
+    let res = exec(&mut deps, &env, "admin", msg).unwrap();
+    assert_eq!(attribute(&res, "migrated"), "true");
+
+    let reputation: ReputationResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetReputation {
+            user: "alice2".to_string(),
+        },
+    );
+    assert_eq!(reputation.total_proofs, 1);
+    let old = QueryMsg::GetReputation {
+        user: "alice".to_string(),
+    };
+    assert!(query(deps.as_ref(), env.clone(), old).is_err());
+
+    let proof: ProofResponse = query_as(&deps, &env, QueryMsg::GetProof { proof_id });
+    assert_eq!(proof.user.as_str(), "alice2");
+    let nfts = user_nfts(&deps, &env, "alice2");
+    assert_eq!(nfts.len(), 1);
+    assert!(user_nfts(&deps, &env, "alice").is_empty());
+
+    let stats: PlatformStats = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetPlatformStats {
+            platform: "LeetCode".to_string(),
+        },
+    );
+    let top_users: Vec<_> = stats.top_users.iter().map(|user| user.as_str()).collect();
+    assert_eq!(top_users, vec!["alice2", "bob"]);
+
+    let leaderboard: Vec<LeaderboardEntry> = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetLeaderboard {
+            platform: None,
+            start_after: None,
+            limit: None,
+        },
+    );
+    assert!(leaderboard
+        .iter()
+        .any(|entry| entry.user.as_str() == "alice2"));
+    assert!(leaderboard
+        .iter()
+        .all(|entry| entry.user.as_str() != "alice"));
+
+    // Nothing to move from an unknown address, and nothing is merged into a used one
+    let msg = ExecuteMsg::MigrateIdentity {
+        old_address: "nobody".to_string(),
+        new_address: "carol".to_string(),
+    };
+    let err = exec(&mut deps, &env, "admin", msg).unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::InvalidIdentityMigration { .. }
+    ));
+
+    let msg = ExecuteMsg::MigrateIdentity {
+        old_address: "alice2".to_string(),
+        new_address: "bob".to_string(),
+    };
+    let err = exec(&mut deps, &env, "admin", msg).unwrap_err();
+    assert!(matches!(err, ContractError::IdentityInUse { .. }));
+}
+
+#[test]
+fn migrate_identity_keeps_declined_endorsements() {
+    let (mut deps, mut env) = setup();
+    onboard(&mut deps, &mut env, "alice");
+    store_proof(&mut deps, &mut env, "bob", "LeetCode", "{}");
+    update_config(
+        &mut deps,
+        &env,
+        r#"{"require_endorsement_acceptance":true,"endorsement_cooldown":1000}"#,
+    );
+
+    let res = endorse(&mut deps, &env, "alice", "bob", "Rust").unwrap();
+    let msg = ExecuteMsg::DeclineEndorsement {
+        endorsement_id: attribute(&res, "endorsement_id"),
+    };
+    exec(&mut deps, &env, "bob", msg).unwrap();
+
+    let msg = ExecuteMsg::MigrateIdentity {
+        old_address: "bob".to_string(),
+        new_address: "bob2".to_string(),
+    };
+    exec(&mut deps, &env, "admin", msg).unwrap();
+
+    // The decline still holds the endorser back under the new address
+    let err = endorse(&mut deps, &env, "alice", "bob2", "Rust").unwrap_err();
+    assert!(matches!(err, ContractError::EndorsementCooldown { .. }));
+}
+
+#[test]
+fn migrate_identity_keeps_withdrawn_endorsements() {
+    let (mut deps, mut env) = setup();
+    onboard(&mut deps, &mut env, "alice");
+    store_proof(&mut deps, &mut env, "bob", "LeetCode", "{}");
+    update_config(
+        &mut deps,
+        &env,
+        r#"{"require_endorsement_acceptance":true,"endorsement_cooldown":1000}"#,
+    );
+
+    let res = endorse(&mut deps, &env, "alice", "bob", "Rust").unwrap();
+    let msg = ExecuteMsg::RevokeEndorsement {
+        endorsement_id: attribute(&res, "endorsement_id"),
+    };
+    exec(&mut deps, &env, "alice", msg).unwrap();
+
+    let msg = ExecuteMsg::MigrateIdentity {
+        old_address: "bob".to_string(),
+        new_address: "bob2".to_string(),
+    };
+    exec(&mut deps, &env, "admin", msg).unwrap();
+
+    // The withdrawn endorsement still holds the endorser back under the new address
+    let err = endorse(&mut deps, &env, "alice", "bob2", "Rust").unwrap_err();
+    assert!(matches!(err, ContractError::EndorsementCooldown { .. }));
+}
+
+#[test]
+fn migrate_identity_rewrites_collusion_counterparties() {
+    let (mut deps, mut env) = setup();
+    onboard(&mut deps, &mut env, "alice");
+    onboard(&mut deps, &mut env, "bob");
+    endorse(&mut deps, &env, "alice", "bob", "Rust").unwrap();
+    endorse(&mut deps, &env, "bob", "alice", "Rust").unwrap();
+
+    let msg = ExecuteMsg::MigrateIdentity {
+        old_address: "alice".to_string(),
+        new_address: "alice2".to_string(),
+    };
+    exec(&mut deps, &env, "admin", msg).unwrap();
+
+    let flags: Vec<CollusionFlag> = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetCollusionFlags {
+            start_after: None,
+            limit: None,
+        },
+    );
+    let flags: Vec<_> = flags
+        .iter()
+        .map(|flag| {
+            let counterparties: Vec<_> = flag.counterparties.iter().map(|a| a.as_str()).collect();
+            (flag.user.as_str(), counterparties)
+        })
+        .collect();
+    assert_eq!(
+        flags,
+        vec![("alice2", vec!["bob"]), ("bob", vec!["alice2"])]
+    );
+}
@@ -0,0 +1,172 @@
+mod common;
+
+use common::{exec, onboard, query_as, setup, store_proof, Deps};
+use cosmwasm_std::{from_json, Decimal, Env, SignedDecimal};
+use skillexify_proof::contract::query;
+use skillexify_proof::msg::{ExecuteMsg, LeaderboardEntry, QueryMsg, UserRankResponse};
+
+// Three accounts at 70 points and two at 35
+fn ranked_accounts() -> (Deps, Env) {
+    let (mut deps, mut env) = setup();
+    for user in ["alice", "bob", "carol"] {
+        onboard(&mut deps, &mut env, user);
+    }
+    for user in ["dave", "erin"] {
+        store_proof(&mut deps, &mut env, user, "LeetCode", "{}");
+    }
+    (deps, env)
+}
+
+fn leaderboard_page(
+    deps: &Deps,
+    env: &Env,
+    start_after: Option<&LeaderboardEntry>,
+) -> Vec<LeaderboardEntry> {
+    let start_after = start_after.map_or("null".to_string(), |entry| {
+        format!(r#"{{"user":"{}","score":"{}"}}"#, entry.user, entry.score)
+    });
+    let msg = from_json(format!(
+        r#"{{"get_leaderboard":{{"start_after":{},"limit":2}}}}"#,
+        start_after
+    ))
+    .unwrap();
+    query_as(deps, env, msg)
+}
+
+fn ranks(entries: &[LeaderboardEntry]) -> Vec<(String, u32)> {
+    entries
+        .iter()
+        .map(|entry| (entry.user.to_string(), entry.rank))
+        .collect()
+}
+
+#[test]
+fn equal_scores_share_a_rank_across_pages() {
+    let (deps, env) = ranked_accounts();
+    let mut entries = leaderboard_page(&deps, &env, None);
+    while let Some(last) = entries.last().cloned() {
+        let page = leaderboard_page(&deps, &env, Some(&last));
+        if page.is_empty() {
+            break;
+        }
+        entries.extend(page);
+    }
+    let ranks: Vec<_> = entries.iter().map(|entry| entry.rank).collect();
+    assert_eq!(ranks, vec![1, 1, 1, 4, 4]);
+}
+
+#[test]
+fn cursor_ranks_are_counted_by_the_contract() {
+    let (deps, env) = ranked_accounts();
+    let first = leaderboard_page(&deps, &env, None);
+
+    // A rank sent along with the cursor is not trusted
+    let msg = from_json(format!(
+        r#"{{"get_leaderboard":{{"start_after":{{"user":"{}","score":"{}","rank":1000}},"limit":2}}}}"#,
+        first[1].user, first[1].score
+    ))
+    .unwrap();
+    let page: Vec<LeaderboardEntry> = query_as(&deps, &env, msg);
+    assert_eq!(
+        page.iter().map(|entry| entry.rank).collect::<Vec<_>>(),
+        vec![1, 4]
+    );
+}
+
+#[test]
+fn user_rank_follows_score_changes() {
+    let (mut deps, env) = ranked_accounts();
+    let rank: UserRankResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetUserRank {
+            user: "dave".to_string(),
+            neighbors: Some(2),
+        },
+    );
+    assert_eq!((rank.global.rank, rank.global.total), (4, 5));
+    assert_eq!(rank.global.percentile, Decimal::zero());
+    let above: Vec<_> = rank.global.above.iter().map(|entry| entry.rank).collect();
+    assert_eq!(above, vec![4, 1]);
+
+    let msg =
+        from_json(r#"{"update_reputation":{"user":"dave","score_delta":"100","reason":"bonus"}}"#)
+            .unwrap();
+    exec(&mut deps, &env, "admin", msg).unwrap();
+    let rank: UserRankResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetUserRank {
+            user: "dave".to_string(),
+            neighbors: Some(2),
+        },
+    );
+    assert_eq!(rank.global.rank, 1);
+    assert_eq!(rank.global.percentile, Decimal::from_ratio(80u128, 1u128));
+    assert_eq!(
+        ranks(&rank.global.below),
+        vec![("carol".to_string(), 2), ("bob".to_string(), 2)]
+    );
+    assert_eq!(rank.platforms[0].rank, 1);
+}
+
+#[test]
+fn leaderboard_pages_use_the_shared_limits() {
+    let (mut deps, mut env) = setup();
+    for n in 0..101 {
+        store_proof(&mut deps, &mut env, &format!("user{n}"), "LeetCode", "{}");
+    }
+    let page = |limit: Option<u32>| -> Vec<LeaderboardEntry> {
+        query_as(
+            &deps,
+            &env,
+            QueryMsg::GetLeaderboard {
+                platform: None,
+                start_after: None,
+                limit,
+            },
+        )
+    };
+    assert_eq!(page(None).len(), 30);
+    assert_eq!(page(Some(1000)).len(), 100);
+}
+
+#[test]
+fn ranks_hold_with_many_distinct_scores_above() {
+    let (mut deps, mut env) = setup();
+    store_proof(&mut deps, &mut env, "last", "LeetCode", "{}");
+
+    // 1001 distinct scores above "last", a thousandth of a point apart
+    for n in 1..=1001 {
+        let user = format!("user{n}");
+        store_proof(&mut deps, &mut env, &user, "LeetCode", "{}");
+        let msg = ExecuteMsg::UpdateReputation {
+            user,
+            score_delta: SignedDecimal::permille(n),
+            reason: "spread".to_string(),
+        };
+        exec(&mut deps, &env, "admin", msg).unwrap();
+    }
+    let rank_of = |user: &str| -> UserRankResponse {
+        let msg = QueryMsg::GetUserRank {
+            user: user.to_string(),
+            neighbors: None,
+        };
+        from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap()
+    };
+    let rank = rank_of("user1");
+    assert_eq!(rank.global.rank, 1001);
+    assert_eq!(rank.global.above[0].rank, 1000);
+    let rank = rank_of("last");
+    assert_eq!(rank.global.rank, 1002);
+    assert_eq!(rank.platforms[0].rank, 1002);
+    assert_eq!(rank.global.total, 1002);
+
+    // Resuming from a cursor deep in the leaderboard counts the same ranks
+    let cursor = &rank_of("user1").global.above[0];
+    let page = leaderboard_page(&deps, &env, Some(cursor));
+    assert_eq!(
+        ranks(&page),
+        vec![("user1".to_string(), 1001), ("last".to_string(), 1002)]
+    );
+}
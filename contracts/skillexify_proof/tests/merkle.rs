@@ -0,0 +1,171 @@
+mod common;
+
+use common::{attribute, exec, query_as, setup, store_proof, Deps};
+use cosmwasm_std::{Env, SignedDecimal};
+use sha2::{Digest, Sha256};
+use skillexify_proof::contract::query;
+use skillexify_proof::msg::{ExecuteMsg, MerkleProofResponse, QueryMsg};
+use skillexify_proof::state::{MerkleJob, MerkleJobPhase, MerkleRoot, ReputationTier};
+
+fn run_merkle_job(deps: &mut Deps, env: &Env, limit: u32) -> bool {
+    let msg = ExecuteMsg::RunMerkleJob { limit: Some(limit) };
+    let res = exec(deps, env, "admin", msg).unwrap();
+    attribute(&res, "done") == "true"
+}
+
+fn merkle_job(deps: &Deps, env: &Env) -> MerkleJob {
+    query_as::<Option<MerkleJob>>(deps, env, QueryMsg::GetMerkleJob {}).unwrap()
+}
+
+fn merkle_proof(deps: &Deps, env: &Env, epoch: u64, user: &str) -> MerkleProofResponse {
+    let msg = QueryMsg::GetMerkleProof {
+        epoch,
+        user: user.to_string(),
+    };
+    query_as(deps, env, msg)
+}
+
+fn verifies(deps: &Deps, env: &Env, epoch: u64, user: &str) -> bool {
+    let leaf = merkle_proof(deps, env, epoch, user);
+    let msg = QueryMsg::VerifyMerkleProof {
+        epoch,
+        user: user.to_string(),
+        score: leaf.score,
+        tier: leaf.tier,
+        proof: leaf.proof,
+    };
+    query_as(deps, env, msg)
+}
+
+fn set_pro_tier(deps: &mut Deps, env: &Env, min_score: u32) {
+    let tiers = vec![ReputationTier {
+        name: "Pro".to_string(),
+        min_score: SignedDecimal::from_ratio(min_score, 1u32),
+    }];
+    let msg = ExecuteMsg::SetReputationTiers { tiers };
+    exec(deps, env, "admin", msg).unwrap();
+}
+
+#[test]
+fn leaves_and_nodes_hash_with_distinct_prefixes() {
+    let (mut deps, mut env) = setup();
+    for user in ["alice", "bob"] {
+        store_proof(&mut deps, &mut env, user, "LeetCode", "{}");
+    }
+    env.block.height += 1;
+    assert!(run_merkle_job(&mut deps, &env, 10));
+
+    let alice = merkle_proof(&deps, &env, 1, "alice");
+    let bob = merkle_proof(&deps, &env, 1, "bob");
+    let leaf_data = format!(
+        "alice|{}|{}",
+        alice.score,
+        alice.tier.clone().unwrap_or_default()
+    );
+    let leaf = Sha256::new()
+        .chain_update([0x00])
+        .chain_update(leaf_data)
+        .finalize();
+    assert_eq!(alice.leaf, hex::encode(leaf));
+
+    let mut pair = [
+        hex::decode(&alice.leaf).unwrap(),
+        hex::decode(&bob.leaf).unwrap(),
+    ];
+    pair.sort();
+    let root = Sha256::new()
+        .chain_update([0x01])
+        .chain_update(&pair[0])
+        .chain_update(&pair[1])
+        .finalize();
+    assert_eq!(alice.root, hex::encode(root));
+    assert_eq!(alice.proof, vec![bob.leaf]);
+}
+
+#[test]
+fn exports_read_accounts_and_tiers_at_the_export_height() {
+    let (mut deps, mut env) = setup();
+    set_pro_tier(&mut deps, &env, 30);
+    for user in ["alice", "bob"] {
+        store_proof(&mut deps, &mut env, user, "LeetCode", "{}");
+    }
+    env.block.height += 1;
+    assert!(!run_merkle_job(&mut deps, &env, 1));
+
+    // Bob moves and the tier is raised while the export is still running
+    env.block.height += 1;
+    let msg = ExecuteMsg::MigrateIdentity {
+        old_address: "bob".to_string(),
+        new_address: "bob2".to_string(),
+    };
+    exec(&mut deps, &env, "admin", msg).unwrap();
+    set_pro_tier(&mut deps, &env, 50);
+    assert!(run_merkle_job(&mut deps, &env, 10));
+
+    let bob = merkle_proof(&deps, &env, 1, "bob");
+    assert_eq!(bob.tier.as_deref(), Some("Pro"));
+    let missing = QueryMsg::GetMerkleProof {
+        epoch: 1,
+        user: "bob2".to_string(),
+    };
+    assert!(query(deps.as_ref(), env.clone(), missing).is_err());
+}
+
+#[test]
+fn next_export_prunes_the_previous_epoch() {
+    let (mut deps, mut env) = setup();
+    for user in ["alice", "bob", "carol"] {
+        store_proof(&mut deps, &mut env, user, "LeetCode", "{}");
+    }
+    env.block.height += 1;
+    assert!(run_merkle_job(&mut deps, &env, 10));
+
+    // Epoch 2 takes three leaves and three parents, then prunes epoch 1's three leaf
+    // records and six nodes
+    env.block.height += 1;
+    assert!(!run_merkle_job(&mut deps, &env, 6));
+    assert_eq!(merkle_job(&deps, &env).phase, MerkleJobPhase::Prune);
+    assert!(!run_merkle_job(&mut deps, &env, 5));
+    assert_eq!(merkle_job(&deps, &env).phase, MerkleJobPhase::Prune);
+    assert!(run_merkle_job(&mut deps, &env, 10));
+
+    let stale = QueryMsg::GetMerkleProof {
+        epoch: 1,
+        user: "alice".to_string(),
+    };
+    assert!(query(deps.as_ref(), env.clone(), stale).is_err());
+    let root: Option<MerkleRoot> =
+        query_as(&deps, &env, QueryMsg::GetMerkleRoot { epoch: Some(1) });
+    assert_eq!(root.map(|root| root.leaf_count), Some(3));
+
+    assert!(verifies(&deps, &env, 2, "alice"));
+}
+
+#[test]
+fn previous_epoch_stays_provable_until_the_next_root_is_published() {
+    let (mut deps, mut env) = setup();
+    for user in ["alice", "bob", "carol"] {
+        store_proof(&mut deps, &mut env, user, "LeetCode", "{}");
+    }
+    env.block.height += 1;
+    assert!(run_merkle_job(&mut deps, &env, 10));
+
+    // Between pages of the next export, epoch 1 still proves against its root
+    env.block.height += 1;
+    assert!(!run_merkle_job(&mut deps, &env, 4));
+    assert_eq!(merkle_job(&deps, &env).phase, MerkleJobPhase::Build);
+    assert!(verifies(&deps, &env, 1, "alice"));
+    let root: Option<MerkleRoot> = query_as(&deps, &env, QueryMsg::GetMerkleRoot { epoch: None });
+    assert_eq!(root.map(|root| root.epoch), Some(1));
+
+    // Publishing epoch 2 starts the prune, which removes leaves ahead of their paths
+    assert!(!run_merkle_job(&mut deps, &env, 4));
+    assert_eq!(merkle_job(&deps, &env).phase, MerkleJobPhase::Prune);
+    assert!(verifies(&deps, &env, 2, "alice"));
+    let stale = QueryMsg::GetMerkleProof {
+        epoch: 1,
+        user: "alice".to_string(),
+    };
+    assert!(query(deps.as_ref(), env.clone(), stale).is_err());
+    assert!(verifies(&deps, &env, 1, "carol"));
+}
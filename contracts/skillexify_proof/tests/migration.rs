@@ -0,0 +1,135 @@
+mod common;
+
+use common::{attribute, exec, onboard, query_as, setup, Deps};
+use cosmwasm_std::{from_json, Addr, Env, Response, Storage};
+use cw2::set_contract_version;
+use skillexify_proof::contract::migrate;
+use skillexify_proof::msg::{LeaderboardEntry, MigrateMsg, QueryMsg, ReputationResponse};
+use skillexify_proof::state::{Achievement, ReputationTier, LEGACY_REPUTATIONS};
+use skillexify_proof::ContractError;
+
+fn run_migrate(deps: &mut Deps, env: &Env) -> Result<Response, ContractError> {
+    migrate(deps.as_mut(), env.clone(), MigrateMsg {})
+}
+
+fn achievements(deps: &Deps, env: &Env) -> Vec<Achievement> {
+    query_as(
+        deps,
+        env,
+        QueryMsg::GetAchievements {
+            start_after: None,
+            limit: None,
+        },
+    )
+}
+
+#[test]
+fn legacy_integer_scores_migrate_to_decimals() {
+    let (mut deps, env) = setup();
+    set_contract_version(&mut deps.storage, "skillexify-proof", "0.1.0").unwrap();
+
+    // Records as 0.1.0 wrote them, with i32 scores
+    deps.storage.set(
+        &LEGACY_REPUTATIONS.key(&Addr::unchecked("bob")),
+        br#"{"user":"bob","score":-7,"total_proofs":1,"endorsements_received":0,"endorsements_given":0,"last_updated":0,"platforms":["LeetCode"]}"#,
+    );
+    deps.storage.set(
+        &LEGACY_REPUTATIONS.key(&Addr::unchecked("carol")),
+        br#"{"user":"carol","score":42,"total_proofs":2,"endorsements_received":0,"endorsements_given":0,"last_updated":0,"platforms":["LeetCode"]}"#,
+    );
+
+    let res = run_migrate(&mut deps, &env).unwrap();
+    assert_eq!(attribute(&res, "from_version"), "0.1.0");
+
+    let reputation: ReputationResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetReputation {
+            user: "bob".to_string(),
+        },
+    );
+    assert_eq!(reputation.score.to_string(), "-7");
+    let tiers: Vec<ReputationTier> = query_as(&deps, &env, QueryMsg::GetReputationTiers {});
+    let tiers: Vec<_> = tiers
+        .iter()
+        .map(|tier| (tier.name.as_str(), tier.min_score.to_string()))
+        .collect();
+    assert_eq!(
+        tiers,
+        vec![
+            ("Novice", "0".to_string()),
+            ("Intermediate", "100".to_string()),
+            ("Expert", "500".to_string()),
+            ("Master", "1000".to_string()),
+        ]
+    );
+
+    let leaderboard: Vec<LeaderboardEntry> = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetLeaderboard {
+            platform: None,
+            start_after: None,
+            limit: None,
+        },
+    );
+    let leaderboard: Vec<_> = leaderboard
+        .iter()
+        .map(|entry| (entry.user.as_str(), entry.score.to_string()))
+        .collect();
+    assert_eq!(
+        leaderboard,
+        vec![("carol", "42".to_string()), ("bob", "-7".to_string())]
+    );
+}
+
+#[test]
+fn migrate_runs_each_step_once() {
+    let (mut deps, env) = setup();
+    for achievement in achievements(&deps, &env) {
+        let msg = from_json(format!(
+            r#"{{"remove_achievement":{{"id":"{}"}}}}"#,
+            achievement.id
+        ))
+        .unwrap();
+        exec(&mut deps, &env, "admin", msg).unwrap();
+    }
+
+    // Upgrading within the same release leaves admin changes alone
+    let res = run_migrate(&mut deps, &env).unwrap();
+    assert_eq!(attribute(&res, "from_version"), env!("CARGO_PKG_VERSION"));
+    assert!(achievements(&deps, &env).is_empty());
+}
+
+#[test]
+fn migrate_rejects_other_contracts_and_downgrades() {
+    let (mut deps, env) = setup();
+    set_contract_version(&mut deps.storage, "other-contract", "0.1.0").unwrap();
+    let err = run_migrate(&mut deps, &env).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidMigration { .. }));
+
+    set_contract_version(&mut deps.storage, "skillexify-proof", "99.0.0").unwrap();
+    let err = run_migrate(&mut deps, &env).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidMigration { .. }));
+}
+
+#[test]
+fn score_overflow_names_the_operation_and_account() {
+    let (mut deps, mut env) = setup();
+    onboard(&mut deps, &mut env, "alice");
+
+    let msg = from_json(
+        r#"{"update_reputation":{"user":"alice","score_delta":"170141183460469231731","reason":"bulk"}}"#,
+    )
+    .unwrap();
+    let err = exec(&mut deps, &env, "admin", msg).unwrap_err();
+    match err {
+        ContractError::ScoreOverflow { operation, account } => {
+            assert_eq!(
+                (operation.as_str(), account.as_str()),
+                ("update_reputation", "alice")
+            );
+        }
+        err => panic!("unexpected error: {}", err),
+    }
+}
@@ -0,0 +1,85 @@
+mod common;
+
+use common::{attribute, endorse, exec, onboard, query_as, setup, update_config, Deps};
+use cosmwasm_std::{from_json, Env, Response};
+use skillexify_proof::msg::{ExecuteMsg, QueryMsg, ReputationResponse};
+use skillexify_proof::ContractError;
+
+fn score(deps: &Deps, env: &Env, user: &str) -> String {
+    let reputation: ReputationResponse = query_as(
+        deps,
+        env,
+        QueryMsg::GetReputation {
+            user: user.to_string(),
+        },
+    );
+    reputation.score.to_string()
+}
+
+fn penalize(deps: &mut Deps, env: &Env, user: &str, points: &str, restrictions: &str) -> Response {
+    let msg = from_json(format!(
+        r#"{{"issue_penalty":{{"user":"{}","score_penalty":"{}","restrictions":{},"reason":"abuse"}}}}"#,
+        user, points, restrictions
+    ))
+    .unwrap();
+    exec(deps, env, "admin", msg).unwrap()
+}
+
+fn floored_at(res: &Response) -> Option<String> {
+    res.attributes
+        .iter()
+        .find(|attr| attr.key == "floored_at")
+        .map(|attr| attr.value.clone())
+}
+
+#[test]
+fn only_penalties_stop_at_the_score_floor() {
+    let (mut deps, mut env) = setup();
+    update_config(&mut deps, &env, r#"{"score_floor":"50"}"#);
+    onboard(&mut deps, &mut env, "alice");
+    assert_eq!(score(&deps, &env, "alice"), "70");
+
+    let res = penalize(&mut deps, &env, "alice", "10", "[]");
+    assert_eq!(floored_at(&res), None);
+    let res = penalize(&mut deps, &env, "alice", "30", "[]");
+    assert_eq!(floored_at(&res).as_deref(), Some("50"));
+    assert_eq!(score(&deps, &env, "alice"), "50");
+
+    // Admin adjustments are not penalties and may go below the floor
+    let msg = from_json(
+        r#"{"update_reputation":{"user":"alice","score_delta":"-20","reason":"correction"}}"#,
+    )
+    .unwrap();
+    exec(&mut deps, &env, "admin", msg).unwrap();
+    assert_eq!(score(&deps, &env, "alice"), "30");
+
+    // A penalty never lifts a score that is already below the floor
+    let res = penalize(&mut deps, &env, "alice", "5", "[]");
+    assert_eq!(floored_at(&res).as_deref(), Some("30"));
+    assert_eq!(score(&deps, &env, "alice"), "30");
+}
+
+#[test]
+fn restricted_accounts_cannot_accept_endorsements() {
+    let (mut deps, mut env) = setup();
+    update_config(
+        &mut deps,
+        &env,
+        r#"{"require_endorsement_acceptance":true}"#,
+    );
+    onboard(&mut deps, &mut env, "alice");
+    onboard(&mut deps, &mut env, "bob");
+    let res = endorse(&mut deps, &env, "alice", "bob", "Rust").unwrap();
+    let endorsement_id = attribute(&res, "endorsement_id");
+
+    penalize(&mut deps, &env, "bob", "0", r#"["endorse"]"#);
+    let msg = ExecuteMsg::AcceptEndorsement {
+        endorsement_id: endorsement_id.clone(),
+    };
+    let err = exec(&mut deps, &env, "bob", msg).unwrap_err();
+    assert!(matches!(err, ContractError::AccountRestricted { .. }));
+
+    // Backing out stays open
+    let msg = ExecuteMsg::DeclineEndorsement { endorsement_id };
+    exec(&mut deps, &env, "bob", msg).unwrap();
+}
@@ -0,0 +1,328 @@
+mod common;
+
+use common::{
+    attribute, exec, has_event, query_as, setup, store_proof, update_config, user_nfts, Deps,
+};
+use cosmwasm_std::Env;
+use cosmwasm_std::SignedDecimal;
+use skillexify_proof::msg::{
+    ConfigResponse, ExecuteMsg, ProofResponse, QueryMsg, ReputationResponse,
+};
+use skillexify_proof::state::PlatformStats;
+use skillexify_proof::ContractError;
+
+// (score, total proofs) of the user, the platform's (proofs, users) and the contract's proofs
+fn proof_totals(deps: &Deps, env: &Env, user: &str) -> (SignedDecimal, u32, (u32, u32), u32) {
+    let reputation: ReputationResponse = query_as(
+        deps,
+        env,
+        QueryMsg::GetReputation {
+            user: user.to_string(),
+        },
+    );
+    let stats: PlatformStats = query_as(
+        deps,
+        env,
+        QueryMsg::GetPlatformStats {
+            platform: "LeetCode".to_string(),
+        },
+    );
+    let config: ConfigResponse = query_as(deps, env, QueryMsg::GetConfig {});
+    (
+        reputation.score,
+        reputation.total_proofs,
+        (stats.total_proofs, stats.total_users),
+        config.total_proofs,
+    )
+}
+
+fn points(points: i64) -> SignedDecimal {
+    SignedDecimal::from_ratio(points, 1i64)
+}
+
+fn evidence(deps: &Deps, env: &Env, token_id: &str) -> Vec<String> {
+    let proofs: Vec<ProofResponse> = query_as(
+        deps,
+        env,
+        QueryMsg::GetNftEvidence {
+            token_id: token_id.to_string(),
+        },
+    );
+    proofs.into_iter().map(|proof| proof.id).collect()
+}
+
+#[test]
+fn revoking_a_proof_recomputes_the_nft() {
+    let (mut deps, mut env) = setup();
+
+    // The only proof earning the level goes away; the remaining one earns nothing
+    let platinum = store_proof(
+        &mut deps,
+        &mut env,
+        "alice",
+        "LeetCode",
+        r#"{"totalSolutions":500}"#,
+    );
+    store_proof(&mut deps, &mut env, "alice", "LeetCode", "{}");
+    assert_eq!(user_nfts(&deps, &env, "alice")[0].skill_level, 4);
+
+    let msg = ExecuteMsg::RevokeProof {
+        proof_id: platinum.clone(),
+        reason: "forged".to_string(),
+    };
+    let res = exec(&mut deps, &env, "admin", msg).unwrap();
+    assert!(has_event(&res, "skill_nft_revoked"));
+    assert!(user_nfts(&deps, &env, "alice").is_empty());
+
+    let proof: ProofResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetProof {
+            proof_id: platinum.clone(),
+        },
+    );
+    assert!(!proof.active);
+    assert_eq!(proof.revocation_reason.as_deref(), Some("forged"));
+    let msg = ExecuteMsg::RevokeProof {
+        proof_id: platinum,
+        reason: "again".to_string(),
+    };
+    let err = exec(&mut deps, &env, "admin", msg).unwrap_err();
+    assert!(matches!(err, ContractError::ProofRevoked { .. }));
+
+    // A weaker proof keeps the NFT at the level it earns
+    let platinum = store_proof(
+        &mut deps,
+        &mut env,
+        "bob",
+        "LeetCode",
+        r#"{"totalSolutions":500}"#,
+    );
+    let silver = store_proof(
+        &mut deps,
+        &mut env,
+        "bob",
+        "LeetCode",
+        r#"{"totalSolutions":160}"#,
+    );
+    let msg = ExecuteMsg::RevokeProof {
+        proof_id: platinum,
+        reason: "withdrawn".to_string(),
+    };
+    let res = exec(&mut deps, &env, "bob", msg).unwrap();
+    assert!(has_event(&res, "skill_nft_downgraded"));
+
+    let nfts = user_nfts(&deps, &env, "bob");
+    assert_eq!(nfts[0].skill_level, 2);
+    assert_eq!(evidence(&deps, &env, &nfts[0].token_id), vec![silver]);
+
+    // Only the proof's owner or the admin may revoke it
+    let proof_id = store_proof(&mut deps, &mut env, "carol", "GitHub", "{}");
+    let msg = ExecuteMsg::RevokeProof {
+        proof_id,
+        reason: "spite".to_string(),
+    };
+    let err = exec(&mut deps, &env, "bob", msg).unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+}
+
+#[test]
+fn expired_proofs_revoke_their_nft() {
+    let (mut deps, mut env) = setup();
+    update_config(&mut deps, &env, r#"{"proof_validity":100}"#);
+    store_proof(
+        &mut deps,
+        &mut env,
+        "alice",
+        "LeetCode",
+        r#"{"totalSolutions":160}"#,
+    );
+    assert_eq!(user_nfts(&deps, &env, "alice")[0].skill_level, 2);
+
+    env.block.time = env.block.time.plus_seconds(100);
+    let res = exec(
+        &mut deps,
+        &env,
+        "carol",
+        ExecuteMsg::ProcessExpiredProofs { limit: None },
+    )
+    .unwrap();
+    assert_eq!(attribute(&res, "processed"), "1");
+    assert!(has_event(&res, "skill_nft_revoked"));
+    assert!(user_nfts(&deps, &env, "alice").is_empty());
+}
+
+#[test]
+fn expired_proofs_stop_backing_the_nft_before_processing() {
+    let (mut deps, mut env) = setup();
+    update_config(&mut deps, &env, r#"{"proof_validity":100}"#);
+    store_proof(
+        &mut deps,
+        &mut env,
+        "alice",
+        "LeetCode",
+        r#"{"totalSolutions":500}"#,
+    );
+    env.block.time = env.block.time.plus_seconds(50);
+    let silver = store_proof(
+        &mut deps,
+        &mut env,
+        "alice",
+        "LeetCode",
+        r#"{"totalSolutions":160}"#,
+    );
+    let token_id = user_nfts(&deps, &env, "alice")[0].token_id.clone();
+    assert_eq!(user_nfts(&deps, &env, "alice")[0].skill_level, 4);
+
+    // The Platinum proof lapsed but nobody processed it yet
+    env.block.time = env.block.time.plus_seconds(50);
+    let nft = &user_nfts(&deps, &env, "alice")[0];
+    assert_eq!((nft.skill_level, nft.proof_count), (2, 1));
+    assert_eq!(evidence(&deps, &env, &token_id), vec![silver.clone()]);
+
+    // Once every proof lapsed, nothing is offered as evidence
+    env.block.time = env.block.time.plus_seconds(50);
+    assert!(evidence(&deps, &env, &token_id).is_empty());
+    assert_eq!(user_nfts(&deps, &env, "alice")[0].proof_count, 0);
+}
+
+#[test]
+fn burned_nfts_are_not_minted_again_by_proofs() {
+    let (mut deps, mut env) = setup();
+    store_proof(
+        &mut deps,
+        &mut env,
+        "alice",
+        "LeetCode",
+        r#"{"totalSolutions":160}"#,
+    );
+    let token_id = user_nfts(&deps, &env, "alice")[0].token_id.clone();
+    let msg = ExecuteMsg::BurnSkillNft { token_id };
+    exec(&mut deps, &env, "alice", msg).unwrap();
+
+    store_proof(
+        &mut deps,
+        &mut env,
+        "alice",
+        "LeetCode",
+        r#"{"totalSolutions":160}"#,
+    );
+    assert!(user_nfts(&deps, &env, "alice").is_empty());
+
+    // Claiming it back lifts the burn, so later proofs raise it again
+    let msg = ExecuteMsg::ClaimSkillNft {
+        platform: "LeetCode".to_string(),
+    };
+    exec(&mut deps, &env, "alice", msg).unwrap();
+    assert_eq!(user_nfts(&deps, &env, "alice")[0].skill_level, 2);
+    store_proof(
+        &mut deps,
+        &mut env,
+        "alice",
+        "LeetCode",
+        r#"{"totalSolutions":500}"#,
+    );
+    assert_eq!(user_nfts(&deps, &env, "alice")[0].skill_level, 4);
+}
+
+#[test]
+fn revoked_nfts_are_not_minted_again_by_proofs_or_claims() {
+    let (mut deps, mut env) = setup();
+    store_proof(
+        &mut deps,
+        &mut env,
+        "alice",
+        "LeetCode",
+        r#"{"totalSolutions":160}"#,
+    );
+    let token_id = user_nfts(&deps, &env, "alice")[0].token_id.clone();
+    let msg = ExecuteMsg::RevokeSkillNft {
+        token_id,
+        reason: "plagiarised".to_string(),
+    };
+    exec(&mut deps, &env, "admin", msg).unwrap();
+
+    store_proof(
+        &mut deps,
+        &mut env,
+        "alice",
+        "LeetCode",
+        r#"{"totalSolutions":500}"#,
+    );
+    assert!(user_nfts(&deps, &env, "alice").is_empty());
+    let msg = ExecuteMsg::ClaimSkillNft {
+        platform: "LeetCode".to_string(),
+    };
+    let err = exec(&mut deps, &env, "alice", msg).unwrap_err();
+    assert!(matches!(err, ContractError::SkillNftRevoked { .. }));
+
+    // Only the admin's mint lifts the revocation
+    let mint = ExecuteMsg::MintSkillNft {
+        recipient: "alice".to_string(),
+        platform: "LeetCode".to_string(),
+        skill_level: 1,
+        token_uri: "ipfs://alice".to_string(),
+    };
+    update_config(&mut deps, &env, r#"{"minters":["minter"]}"#);
+    let err = exec(&mut deps, &env, "minter", mint.clone()).unwrap_err();
+    assert!(matches!(err, ContractError::SkillNftRevoked { .. }));
+    exec(&mut deps, &env, "admin", mint).unwrap();
+    store_proof(
+        &mut deps,
+        &mut env,
+        "alice",
+        "LeetCode",
+        r#"{"totalSolutions":500}"#,
+    );
+    assert_eq!(user_nfts(&deps, &env, "alice")[0].skill_level, 4);
+}
+
+#[test]
+fn revoked_and_expired_proofs_give_their_points_back() {
+    let (mut deps, mut env) = setup();
+    let first = store_proof(&mut deps, &mut env, "alice", "LeetCode", "{}");
+    assert_eq!(
+        proof_totals(&deps, &env, "alice"),
+        (points(35), 1, (1, 1), 1)
+    );
+
+    // Revoking and storing again gains nothing
+    let msg = ExecuteMsg::RevokeProof {
+        proof_id: first,
+        reason: "resubmitting".to_string(),
+    };
+    exec(&mut deps, &env, "alice", msg).unwrap();
+    assert_eq!(
+        proof_totals(&deps, &env, "alice"),
+        (points(0), 0, (0, 0), 0)
+    );
+    store_proof(&mut deps, &mut env, "alice", "LeetCode", "{}");
+    assert_eq!(
+        proof_totals(&deps, &env, "alice"),
+        (points(35), 1, (1, 1), 1)
+    );
+
+    // Expiry takes the proofs back once processed; revoking a processed proof takes nothing
+    update_config(&mut deps, &env, r#"{"proof_validity":100}"#);
+    let expiring = store_proof(&mut deps, &mut env, "alice", "LeetCode", "{}");
+    assert_eq!(
+        proof_totals(&deps, &env, "alice"),
+        (points(45), 2, (2, 1), 2)
+    );
+    env.block.time = env.block.time.plus_seconds(100);
+    let msg = ExecuteMsg::ProcessExpiredProofs { limit: None };
+    exec(&mut deps, &env, "carol", msg).unwrap();
+    assert_eq!(
+        proof_totals(&deps, &env, "alice"),
+        (points(35), 1, (1, 1), 1)
+    );
+    let msg = ExecuteMsg::RevokeProof {
+        proof_id: expiring,
+        reason: "stale".to_string(),
+    };
+    exec(&mut deps, &env, "alice", msg).unwrap();
+    assert_eq!(
+        proof_totals(&deps, &env, "alice"),
+        (points(35), 1, (1, 1), 1)
+    );
+}
@@ -0,0 +1,134 @@
+mod common;
+
+use common::{exec, has_event, query_as, setup, store_proof, Deps, PROOF_HASH};
+use cosmwasm_std::{Env, Response, SignedDecimal};
+use skillexify_proof::msg::{ExecuteMsg, QueryMsg, ReputationSnapshotResponse};
+use skillexify_proof::state::ReputationTier;
+
+fn set_tiers(deps: &mut Deps, env: &Env, pro_score: u32) {
+    let tiers = [("Novice", 0), ("Pro", pro_score)]
+        .into_iter()
+        .map(|(name, min_score)| ReputationTier {
+            name: name.to_string(),
+            min_score: SignedDecimal::from_ratio(min_score, 1u32),
+        })
+        .collect();
+    let msg = ExecuteMsg::SetReputationTiers { tiers };
+    exec(deps, env, "admin", msg).unwrap();
+}
+
+fn tiers_at(deps: &Deps, env: &Env, height: u64) -> Vec<(String, Option<String>)> {
+    let snapshots: Vec<ReputationSnapshotResponse> = query_as(
+        deps,
+        env,
+        QueryMsg::GetReputationsAtHeight {
+            height,
+            start_after: None,
+            limit: None,
+        },
+    );
+    snapshots
+        .into_iter()
+        .map(|snapshot| (snapshot.user.to_string(), snapshot.tier))
+        .collect()
+}
+
+fn store_alice_proof(deps: &mut Deps, env: &Env, platform: &str) -> Response {
+    let msg = ExecuteMsg::StoreProof {
+        platform: platform.to_string(),
+        username: "alice".to_string(),
+        skill_data: "{}".to_string(),
+        proof_hash: PROOF_HASH.to_string(),
+        metadata: None,
+    };
+    exec(deps, env, "alice", msg).unwrap()
+}
+
+#[test]
+fn past_heights_keep_moved_accounts_and_old_tiers() {
+    let (mut deps, mut env) = setup();
+    store_proof(&mut deps, &mut env, "alice", "LeetCode", "{}");
+    store_proof(&mut deps, &mut env, "bob", "LeetCode", "{}");
+    set_tiers(&mut deps, &env, 30);
+
+    // Alice moves and the thresholds are raised in the same later block
+    env.block.height += 1;
+    let then = env.block.height;
+    let msg = ExecuteMsg::MigrateIdentity {
+        old_address: "alice".to_string(),
+        new_address: "alice2".to_string(),
+    };
+    exec(&mut deps, &env, "admin", msg).unwrap();
+    set_tiers(&mut deps, &env, 50);
+    env.block.height += 1;
+
+    let pro = Some("Pro".to_string());
+    assert_eq!(
+        tiers_at(&deps, &env, then),
+        vec![("alice".to_string(), pro.clone()), ("bob".to_string(), pro)]
+    );
+    let novice = Some("Novice".to_string());
+    assert_eq!(
+        tiers_at(&deps, &env, env.block.height),
+        vec![
+            ("alice2".to_string(), novice.clone()),
+            ("bob".to_string(), novice)
+        ]
+    );
+
+    let snapshot: Option<ReputationSnapshotResponse> = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetReputationAtHeight {
+            user: "alice".to_string(),
+            height: then,
+        },
+    );
+    assert_eq!(snapshot.unwrap().tier.as_deref(), Some("Pro"));
+}
+
+// (old_tier, new_tier) of the `tier_changed` event, if any
+fn tier_change(res: &Response) -> Option<(String, String)> {
+    let event = res.events.iter().find(|event| event.ty == "tier_changed")?;
+    let value = |key: &str| {
+        event
+            .attributes
+            .iter()
+            .find(|attr| attr.key == key)
+            .map(|attr| attr.value.clone())
+            .unwrap()
+    };
+    Some((value("old_tier"), value("new_tier")))
+}
+
+fn update_alice(deps: &mut Deps, env: &Env, score_delta: i64) -> Response {
+    let msg = ExecuteMsg::UpdateReputation {
+        user: "alice".to_string(),
+        score_delta: SignedDecimal::from_ratio(score_delta, 1),
+        reason: "adjustment".to_string(),
+    };
+    exec(deps, env, "admin", msg).unwrap()
+}
+
+#[test]
+fn tier_changes_are_reported_after_sign_up() {
+    let (mut deps, env) = setup();
+    set_tiers(&mut deps, &env, 50);
+
+    // Signing up lands in Novice without a change event
+    let res = store_alice_proof(&mut deps, &env, "LeetCode");
+    assert!(!has_event(&res, "tier_changed"));
+    let res = store_alice_proof(&mut deps, &env, "GitHub");
+    let change = ("Novice".to_string(), "Pro".to_string());
+    assert_eq!(tier_change(&res), Some(change));
+
+    // Dropping below every tier and climbing back are both changes
+    let res = update_alice(&mut deps, &env, -1000);
+    let change = ("Pro".to_string(), "none".to_string());
+    assert_eq!(tier_change(&res), Some(change));
+    let res = update_alice(&mut deps, &env, -1);
+    assert_eq!(tier_change(&res), None);
+    let res = update_alice(&mut deps, &env, 1001);
+    let change = ("none".to_string(), "Pro".to_string());
+    assert_eq!(tier_change(&res), Some(change));
+}
@@ -0,0 +1,83 @@
+mod common;
+
+use common::{attribute, endorse, exec, onboard, query_as, setup, Deps};
+use cosmwasm_std::{Decimal, Env, Response};
+use skillexify_proof::msg::{ExecuteMsg, QueryMsg, ReputationResponse};
+use skillexify_proof::state::{TrustJob, TrustJobPhase};
+
+fn run_trust_job(deps: &mut Deps, env: &Env, limit: u32, restart: bool) -> Response {
+    let msg = ExecuteMsg::RunTrustJob {
+        iterations: Some(5),
+        damping: None,
+        limit: Some(limit),
+        restart,
+    };
+    exec(deps, env, "admin", msg).unwrap()
+}
+
+fn trust_job(deps: &Deps, env: &Env) -> TrustJob {
+    query_as::<Option<TrustJob>>(deps, env, QueryMsg::GetTrustJob {}).unwrap()
+}
+
+#[test]
+fn trust_job_resumes_across_new_endorsements() {
+    let (mut deps, mut env) = setup();
+    for user in ["alice", "bob"] {
+        onboard(&mut deps, &mut env, user);
+    }
+    endorse(&mut deps, &env, "alice", "dave", "Rust").unwrap();
+
+    // Step the job one record at a time until it starts propagating
+    while trust_job_phase(&mut deps, &env) != TrustJobPhase::Propagate {
+        run_trust_job(&mut deps, &env, 1, false);
+    }
+
+    // Bob had no outgoing weight when the job summed them up
+    endorse(&mut deps, &env, "bob", "dave", "Rust").unwrap();
+    loop {
+        let res = run_trust_job(&mut deps, &env, 2, false);
+        if attribute(&res, "done") == "true" {
+            break;
+        }
+    }
+
+    let reputation: ReputationResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetReputation {
+            user: "dave".to_string(),
+        },
+    );
+    assert!(reputation.trust_score > Decimal::zero());
+    assert!(reputation.trust_score < Decimal::one());
+}
+
+// Current phase, starting a job first when none is running
+fn trust_job_phase(deps: &mut Deps, env: &Env) -> TrustJobPhase {
+    let job = query_as::<Option<TrustJob>>(deps, env, QueryMsg::GetTrustJob {});
+    match job {
+        Some(job) => job.phase,
+        None => {
+            run_trust_job(deps, env, 1, false);
+            trust_job(deps, env).phase
+        }
+    }
+}
+
+#[test]
+fn trust_job_restart_abandons_the_running_job() {
+    let (mut deps, mut env) = setup();
+    onboard(&mut deps, &mut env, "alice");
+    endorse(&mut deps, &env, "alice", "dave", "Rust").unwrap();
+
+    // Alice and Dave are counted, then Alice is seeded
+    run_trust_job(&mut deps, &env, 3, false);
+    assert_eq!(trust_job(&deps, &env).phase, TrustJobPhase::Init);
+
+    env.block.time = env.block.time.plus_seconds(60);
+    let res = run_trust_job(&mut deps, &env, 1, true);
+    assert_eq!(attribute(&res, "done"), "false");
+    let job = trust_job(&deps, &env);
+    assert_eq!(job.phase, TrustJobPhase::CountSeeds);
+    assert_eq!(job.started_at, env.block.time.seconds());
+}
@@ -35,7 +35,7 @@ export interface UserProof {
 
 export interface UserReputation {
   user: string;
-  score: number;
+  score: string; // signed fixed-point decimal, e.g. "-12.5"
   totalProofs: number;
   endorsementsReceived: number;
   endorsementsGiven: number;
@@ -64,7 +64,7 @@ export interface SkillNFT {
 
 export interface LeaderboardEntry {
   user: string;
-  score: number;
+  score: string; // signed fixed-point decimal, e.g. "-12.5"
   rank: number;
   primaryPlatform: string;
   totalProofs: number;